@@ -0,0 +1,148 @@
+/// the volume settings of the whole app
+use bevy::{audio::Volume, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::functions::{ConfigError, load_config, save_config};
+
+// audio constanten
+const AUDIO_SETTINGS_FILE: &str = "audio.ron";
+
+/// the volumes a player can change
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum VolumeChannel {
+    Master,
+    Music,
+    Effects,
+}
+
+impl VolumeChannel {
+    pub const ALL: [VolumeChannel; 3] = [
+        VolumeChannel::Master,
+        VolumeChannel::Music,
+        VolumeChannel::Effects,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            VolumeChannel::Master => "Master",
+            VolumeChannel::Music => "Music",
+            VolumeChannel::Effects => "Sound effects",
+        }
+    }
+}
+
+/// the volumes from 0 to 1, saved in the config directory
+/// the master volume is the global volume, the others are set on every sound
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct AudioSettings {
+    pub master: f32,
+    pub music: f32,
+    pub effects: f32,
+}
+
+impl Default for AudioSettings {
+    fn default() -> Self {
+        Self {
+            master: 1.0,
+            music: 0.5,
+            effects: 0.8,
+        }
+    }
+}
+
+impl AudioSettings {
+    pub fn get(&self, channel: VolumeChannel) -> f32 {
+        match channel {
+            VolumeChannel::Master => self.master,
+            VolumeChannel::Music => self.music,
+            VolumeChannel::Effects => self.effects,
+        }
+    }
+
+    pub fn set(&mut self, channel: VolumeChannel, volume: f32) {
+        let volume = volume.clamp(0.0, 1.0);
+        match channel {
+            VolumeChannel::Master => self.master = volume,
+            VolumeChannel::Music => self.music = volume,
+            VolumeChannel::Effects => self.effects = volume,
+        }
+    }
+
+    /// keeps the volumes from 0 to 1, for settings from a changed file
+    /// volumes which are no number are set back to the default
+    pub fn clamp(&mut self) {
+        let default = Self::default();
+        for channel in VolumeChannel::ALL {
+            let volume = self.get(channel);
+            let volume = if volume.is_nan() {
+                default.get(channel)
+            } else {
+                volume
+            };
+            self.set(channel, volume);
+        }
+    }
+
+    /// settings for a music note which is removed after it was played
+    pub fn music_playback(&self) -> PlaybackSettings {
+        PlaybackSettings::DESPAWN.with_volume(Volume::Linear(self.music))
+    }
+
+    /// settings for a sound effect with the speed, a higher speed gives a higher pitch
+    pub fn effect_playback(&self, speed: f32) -> PlaybackSettings {
+        PlaybackSettings::DESPAWN
+            .with_volume(Volume::Linear(self.effects))
+            .with_speed(speed)
+    }
+}
+
+/// plugin for the volume settings
+pub struct AudioSettingsPlugin;
+impl Plugin for AudioSettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AudioSettings>()
+            .add_systems(Startup, load_audio_settings)
+            .add_systems(
+                Update,
+                apply_master_volume.run_if(resource_changed::<AudioSettings>),
+            );
+    }
+}
+
+/// reads the saved audio settings, without a file the default volumes are used
+fn load_audio_settings(mut commands: Commands) {
+    match load_config::<AudioSettings>(AUDIO_SETTINGS_FILE) {
+        Ok(Some(mut settings)) => {
+            settings.clamp();
+            commands.insert_resource(settings);
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// writes the audio settings into the config directory
+pub fn save_audio_settings(settings: &AudioSettings) -> Result<(), ConfigError> {
+    save_config(AUDIO_SETTINGS_FILE, settings)
+}
+
+/// the master volume changes all sounds which start after it
+fn apply_master_volume(settings: Res<AudioSettings>, mut global_volume: ResMut<GlobalVolume>) {
+    global_volume.volume = Volume::Linear(settings.master);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loaded_volumes_are_clamped() {
+        let mut settings: AudioSettings =
+            ron::from_str("(master: 3.0, music: -1.0, effects: NaN)").unwrap();
+        settings.clamp();
+
+        assert_eq!(settings.master, 1.0);
+        assert_eq!(settings.music, 0.0);
+        assert_eq!(settings.effects, AudioSettings::default().effects);
+    }
+}
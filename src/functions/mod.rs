@@ -1,4 +1,11 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
 use bevy::prelude::*;
+use serde::{Serialize, de::DeserializeOwned};
+use thiserror::Error;
 
 use crate::structs::GeneralTimer;
 
@@ -11,3 +18,64 @@ pub fn despawn_screen<T: Component>(to_despawn: Query<Entity, With<T>>, mut comm
 pub fn countdown(time: Res<Time>, mut timer: ResMut<GeneralTimer>) -> bool {
     timer.tick(time.delta()).finished()
 }
+
+/// folder for the settings of the games in the config directory of the user
+pub fn config_dir() -> PathBuf {
+    dirs::config_dir().unwrap_or_default().join("games")
+}
+
+/// folder for the saved data of the games in the data directory of the user
+pub fn data_dir() -> PathBuf {
+    dirs::data_dir().unwrap_or_default().join("games")
+}
+
+/// errors while saving or loading a ron file
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Could not access {0}: {1}")]
+    Io(String, std::io::Error),
+    #[error("Could not parse {0}: {1}")]
+    Parse(String, ron::error::SpannedError),
+    #[error("Could not write {0}: {1}")]
+    Write(String, ron::Error),
+}
+
+/// reads the file of the config directory, none if there is no file yet
+pub fn load_config<T: DeserializeOwned>(name: &str) -> Result<Option<T>, ConfigError> {
+    load_ron(&config_dir().join(name), name)
+}
+
+/// writes the file into the config directory
+pub fn save_config<T: Serialize>(name: &str, value: &T) -> Result<(), ConfigError> {
+    save_ron(&config_dir().join(name), name, value)
+}
+
+/// reads the file of the data directory, none if there is no file yet
+pub fn load_data<T: DeserializeOwned>(name: &str) -> Result<Option<T>, ConfigError> {
+    load_ron(&data_dir().join(name), name)
+}
+
+/// writes the file into the data directory
+pub fn save_data<T: Serialize>(name: &str, value: &T) -> Result<(), ConfigError> {
+    save_ron(&data_dir().join(name), name, value)
+}
+
+fn load_ron<T: DeserializeOwned>(path: &Path, name: &str) -> Result<Option<T>, ConfigError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let bytes = fs::read(path).map_err(|e| ConfigError::Io(name.to_string(), e))?;
+    ron::de::from_bytes(&bytes)
+        .map(Some)
+        .map_err(|e| ConfigError::Parse(name.to_string(), e))
+}
+
+fn save_ron<T: Serialize>(path: &Path, name: &str, value: &T) -> Result<(), ConfigError> {
+    let text = ron::ser::to_string_pretty(value, ron::ser::PrettyConfig::default())
+        .map_err(|e| ConfigError::Write(name.to_string(), e))?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| ConfigError::Io(name.to_string(), e))?;
+    }
+    fs::write(path, text).map_err(|e| ConfigError::Io(name.to_string(), e))
+}
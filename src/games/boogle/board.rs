@@ -0,0 +1,165 @@
+/// the letters of the boogle board and the paths of words through it
+use bevy::prelude::*;
+use rand::prelude::*;
+
+/// position of a die on the board
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub struct Cell {
+    pub row: usize,
+    pub column: usize,
+}
+
+impl Cell {
+    pub fn new(row: usize, column: usize) -> Self {
+        Self { row, column }
+    }
+
+    /// true for the eight cells around, diagonals included
+    pub fn is_adjacent(self, other: Cell) -> bool {
+        self != other
+            && self.row.abs_diff(other.row) <= 1
+            && self.column.abs_diff(other.column) <= 1
+    }
+}
+
+/// reasons why a word has no path on the board
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PathError {
+    /// the letter is on no die, or on fewer dice than the word needs
+    MissingLetter(char),
+    /// all letters are there, but not next to each other
+    NotConnected,
+}
+
+impl PathError {
+    pub fn message(self) -> String {
+        match self {
+            PathError::MissingLetter(letter) => format!("No {} on the board", letter),
+            PathError::NotConnected => "The letters are not connected".to_string(),
+        }
+    }
+}
+
+/// the square board, the letters are saved row by row
+#[derive(Resource, Clone, Eq, PartialEq, Debug)]
+pub struct Board {
+    size: usize,
+    letters: Vec<char>,
+}
+
+impl Board {
+    /// a board of the rows, none if the rows do not form a square
+    pub fn from_rows(rows: &[&str]) -> Option<Self> {
+        let size = rows.len();
+        let letters: Vec<char> = rows
+            .iter()
+            .flat_map(|row| row.chars().flat_map(char::to_uppercase))
+            .collect();
+        let square = rows.iter().all(|row| row.chars().count() == size);
+        (size > 0 && square).then_some(Self { size, letters })
+    }
+
+    /// one random letter of every die in a random order
+    /// the number of dice has to be a square number
+    pub fn roll(dice: &[[char; 6]]) -> Self {
+        let mut rng = rand::rng();
+        let mut letters: Vec<char> = dice
+            .iter()
+            .map(|die| *die.choose(&mut rng).expect("a die has sides"))
+            .collect();
+        letters.shuffle(&mut rng);
+        Self {
+            size: letters.len().isqrt(),
+            letters,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn get(&self, cell: Cell) -> Option<char> {
+        if cell.row >= self.size || cell.column >= self.size {
+            return None;
+        }
+        self.letters
+            .get(cell.row * self.size + cell.column)
+            .copied()
+    }
+
+    /// the letters of every row from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[char]> {
+        self.letters.chunks(self.size)
+    }
+
+    /// all cells row by row
+    pub fn cells(&self) -> impl Iterator<Item = Cell> + use<> {
+        let size = self.size;
+        (0..size * size).map(move |index| Cell::new(index / size, index % size))
+    }
+
+    /// the cells around the cell which are on the board
+    pub fn neighbours(&self, cell: Cell) -> impl Iterator<Item = Cell> + use<> {
+        let size = self.size;
+        let rows = cell.row.saturating_sub(1)..=(cell.row + 1).min(size - 1);
+        rows.flat_map(move |row| {
+            let columns = cell.column.saturating_sub(1)..=(cell.column + 1).min(size - 1);
+            columns.map(move |column| Cell::new(row, column))
+        })
+        .filter(move |other| *other != cell)
+    }
+
+    /// the dice which spell the word, every die next to the one before and none used twice
+    /// the search tries every start die and goes back when a path ends
+    pub fn find_path(&self, word: &str) -> Result<Vec<Cell>, PathError> {
+        let word: Vec<char> = word.chars().flat_map(char::to_uppercase).collect();
+        if let Some(letter) = self.missing_letter(&word) {
+            return Err(PathError::MissingLetter(letter));
+        }
+
+        let mut path = Vec::with_capacity(word.len());
+        let mut used = vec![false; self.letters.len()];
+        for start in self.cells() {
+            if self.extend_path(&word, start, &mut path, &mut used) {
+                return Ok(path);
+            }
+        }
+        Err(PathError::NotConnected)
+    }
+
+    /// the first letter of the word which is on fewer dice than the word needs
+    fn missing_letter(&self, word: &[char]) -> Option<char> {
+        word.iter().copied().find(|letter| {
+            let needed = word.iter().filter(|other| *other == letter).count();
+            let available = self.letters.iter().filter(|other| *other == letter).count();
+            available < needed
+        })
+    }
+
+    /// adds the cell to the path if it has the next letter and goes on with its neighbours
+    fn extend_path(
+        &self,
+        word: &[char],
+        cell: Cell,
+        path: &mut Vec<Cell>,
+        used: &mut [bool],
+    ) -> bool {
+        let index = cell.row * self.size + cell.column;
+        if used[index] || self.get(cell) != word.get(path.len()).copied() {
+            return false;
+        }
+
+        path.push(cell);
+        used[index] = true;
+        if path.len() == word.len()
+            || self
+                .neighbours(cell)
+                .any(|next| self.extend_path(word, next, path, used))
+        {
+            return true;
+        }
+        path.pop();
+        used[index] = false;
+        false
+    }
+}
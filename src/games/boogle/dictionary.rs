@@ -0,0 +1,313 @@
+/// the word lists of boogle, one for every language
+use std::path::Path;
+
+use bevy::{
+    asset::{
+        AssetLoader, AssetPath, LoadContext, ReadAssetBytesError,
+        io::{AssetSource, AssetSourceBuilder, Reader},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::functions::{ConfigError, load_config, save_config};
+use crate::games::boogle::{hunspell::Affixes, trie::Trie};
+
+// dictionary constanten
+const DICTIONARY_SETTINGS_FILE: &str = "boogle_dictionary.ron";
+/// small word lists of the game, used if no hunspell dictionary is installed
+const GERMAN_WORDS: &str = "dictionaries/de.words.txt";
+const ENGLISH_WORDS: &str = "dictionaries/en.words.txt";
+/// folder of the installed hunspell dictionaries
+const SYSTEM_DICTIONARIES: &str = "/usr/share/hunspell";
+/// asset source of the installed hunspell dictionaries
+const SYSTEM_SOURCE: &str = "hunspell";
+const LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// the languages of the dictionaries
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq, Hash, Debug)]
+pub enum Language {
+    #[default]
+    German,
+    English,
+}
+
+impl Language {
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::German => "German",
+            Language::English => "English",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Language::German => Language::English,
+            Language::English => Language::German,
+        }
+    }
+
+    /// the word list of the game
+    fn word_list(self) -> &'static str {
+        match self {
+            Language::German => GERMAN_WORDS,
+            Language::English => ENGLISH_WORDS,
+        }
+    }
+
+    /// the installed hunspell dictionary if there is one, otherwise the word list of the game
+    fn default_dictionary(self) -> String {
+        let name = match self {
+            Language::German => "de_DE",
+            Language::English => "en_US",
+        };
+        let dic = Path::new(SYSTEM_DICTIONARIES)
+            .join(name)
+            .with_extension("dic");
+        if dic.exists() && dic.with_extension("aff").exists() {
+            format!("{}://{}.dic", SYSTEM_SOURCE, name)
+        } else {
+            self.word_list().to_string()
+        }
+    }
+}
+
+/// the letters on the dice for a typed or listed letter
+/// umlauts are written out and ß becomes SS, other letters are on no die
+pub fn normalize_letter(letter: char) -> Option<&'static str> {
+    match letter {
+        'a'..='z' | 'A'..='Z' => {
+            let index = usize::from(letter.to_ascii_uppercase() as u8 - b'A');
+            Some(&LETTERS[index..=index])
+        }
+        'ä' | 'Ä' => Some("AE"),
+        'ö' | 'Ö' => Some("OE"),
+        'ü' | 'Ü' => Some("UE"),
+        'ß' | 'ẞ' => Some("SS"),
+        _ => None,
+    }
+}
+
+/// the word in the letters of the dice, none if it has other characters
+pub fn normalize_word(word: &str) -> Option<String> {
+    let word = word.trim();
+    if word.is_empty() {
+        return None;
+    }
+    word.chars().map(normalize_letter).collect()
+}
+
+/// the normalized words of a word list
+#[derive(Asset, TypePath, Default, Clone, Debug)]
+pub struct Dictionary {
+    trie: Trie,
+}
+
+impl Dictionary {
+    /// words with characters which are on no die are left out
+    pub fn from_words<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        Self {
+            trie: Trie::new(words.into_iter().filter_map(normalize_word)),
+        }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        normalize_word(word).is_some_and(|word| self.trie.contains(&word))
+    }
+
+    pub fn trie(&self) -> &Trie {
+        &self.trie
+    }
+}
+
+/// reads a plain word list with one word in every line, lines with '#' are comments
+#[derive(Default)]
+struct WordListLoader;
+
+/// errors while loading a word list
+#[derive(Debug, Error)]
+enum WordListLoaderError {
+    #[error("Could not load word list: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Word list is no UTF-8 text: {0}")]
+    Encoding(#[from] std::string::FromUtf8Error),
+}
+
+impl AssetLoader for WordListLoader {
+    type Asset = Dictionary;
+    type Settings = ();
+    type Error = WordListLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = String::from_utf8(bytes)?;
+        Ok(Dictionary::from_words(
+            text.lines()
+                .filter(|line| !line.trim_start().starts_with('#')),
+        ))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["words.txt"]
+    }
+}
+
+/// reads a hunspell .dic file and expands its words with the .aff file next to it
+#[derive(Default)]
+struct HunspellLoader;
+
+/// errors while loading a hunspell dictionary
+#[derive(Debug, Error)]
+enum HunspellLoaderError {
+    #[error("Could not load hunspell dictionary: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not load hunspell affixes: {0}")]
+    Affixes(#[from] ReadAssetBytesError),
+}
+
+impl AssetLoader for HunspellLoader {
+    type Asset = Dictionary;
+    type Settings = ();
+    type Error = HunspellLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        // the .aff file is read from the same source as the .dic file
+        let aff_file = load_context.path().with_extension("aff");
+        let aff_path = AssetPath::from_path(&aff_file)
+            .with_source(load_context.asset_path().source().clone_owned())
+            .clone_owned();
+        let affixes = Affixes::parse(&load_context.read_asset_bytes(aff_path).await?);
+        let words = affixes.expand(&affixes.decode(&bytes));
+        Ok(Dictionary::from_words(words.iter().map(String::as_str)))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["dic"]
+    }
+}
+
+/// the chosen language and the dictionary file of every language, saved in the config directory
+/// the files are in the assets folder or with "hunspell://" in the folder of the installed
+/// hunspell dictionaries, word lists end with .words.txt and hunspell files with .dic
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct DictionarySettings {
+    pub language: Language,
+    pub german: String,
+    pub english: String,
+}
+
+impl Default for DictionarySettings {
+    fn default() -> Self {
+        Self {
+            language: Language::default(),
+            german: Language::German.default_dictionary(),
+            english: Language::English.default_dictionary(),
+        }
+    }
+}
+
+impl DictionarySettings {
+    /// the file of the chosen language
+    pub fn path(&self) -> &str {
+        match self.language {
+            Language::German => &self.german,
+            Language::English => &self.english,
+        }
+    }
+}
+
+/// the dictionary of the chosen language
+#[derive(Resource)]
+pub struct CurrentDictionary(pub Handle<Dictionary>);
+
+/// makes the installed hunspell dictionaries loadable with "hunspell://",
+/// it has to be added before the DefaultPlugins
+pub struct SystemDictionaryPlugin;
+impl Plugin for SystemDictionaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_asset_source(
+            SYSTEM_SOURCE,
+            AssetSourceBuilder::default().with_reader(AssetSource::get_default_reader(
+                SYSTEM_DICTIONARIES.to_string(),
+            )),
+        );
+    }
+}
+
+/// plugin for loading the dictionaries
+pub struct DictionaryPlugin;
+impl Plugin for DictionaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<Dictionary>()
+            .init_asset_loader::<WordListLoader>()
+            .init_asset_loader::<HunspellLoader>()
+            .init_resource::<DictionarySettings>()
+            .add_systems(Startup, load_dictionary_settings)
+            .add_systems(
+                Update,
+                (
+                    load_dictionary.run_if(resource_changed::<DictionarySettings>),
+                    fall_back_to_word_list.run_if(resource_exists::<CurrentDictionary>),
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// reads the saved dictionary settings, without a file german is used
+fn load_dictionary_settings(mut commands: Commands) {
+    match load_config::<DictionarySettings>(DICTIONARY_SETTINGS_FILE) {
+        Ok(Some(settings)) => commands.insert_resource(settings),
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// writes the dictionary settings into the config directory
+pub fn save_dictionary_settings(settings: &DictionarySettings) -> Result<(), ConfigError> {
+    save_config(DICTIONARY_SETTINGS_FILE, settings)
+}
+
+/// starts loading the dictionary of the chosen language
+fn load_dictionary(
+    mut commands: Commands,
+    settings: Res<DictionarySettings>,
+    asset_server: Res<AssetServer>,
+) {
+    commands.insert_resource(CurrentDictionary(asset_server.load(settings.path())));
+}
+
+/// loads the word list of the game if the chosen dictionary could not be loaded
+fn fall_back_to_word_list(
+    mut commands: Commands,
+    settings: Res<DictionarySettings>,
+    current: Res<CurrentDictionary>,
+    asset_server: Res<AssetServer>,
+) {
+    if !asset_server.load_state(&current.0).is_failed() {
+        return;
+    }
+    let word_list = asset_server.load(settings.language.word_list());
+    if current.0 != word_list {
+        eprintln!(
+            "Could not load the dictionary {}, using {}",
+            settings.path(),
+            settings.language.word_list()
+        );
+        commands.insert_resource(CurrentDictionary(word_list));
+    }
+}
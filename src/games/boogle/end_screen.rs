@@ -0,0 +1,167 @@
+/// the screen after a boogle round is over
+use bevy::prelude::*;
+
+use crate::enums::GameState;
+use crate::functions::despawn_screen;
+use crate::games::boogle::game::{BoardWords, BoogleRound, BoogleState};
+use crate::menus::{NORMAL_BUTTON, button_system, menu_button_node};
+
+/// all elements on the end screen need this
+#[derive(Component)]
+struct OnEndScreen;
+
+/// button actions of the end screen
+#[derive(Component)]
+enum EndScreenButtonAction {
+    NewBoard,
+    BackToMenu,
+}
+
+/// plugin for the end screen
+pub struct EndScreenPlugin;
+impl Plugin for EndScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(BoogleState::RoundOver), end_screen_setup)
+            .add_systems(
+                OnExit(BoogleState::RoundOver),
+                despawn_screen::<OnEndScreen>,
+            )
+            .add_systems(
+                Update,
+                (button_system, end_screen_action).run_if(in_state(BoogleState::RoundOver)),
+            );
+    }
+}
+
+/// the words with their points under the title
+fn words_text<'a>(
+    title: &str,
+    words: impl Iterator<Item = &'a str>,
+    round: &BoogleRound,
+) -> String {
+    let mut text = title.to_string();
+    for word in words {
+        text.push_str(&format!("\n{} ({})", word, round.points(word)));
+    }
+    text
+}
+
+/// spawns the score, the found and the missed words and the buttons
+fn end_screen_setup(mut commands: Commands, round: Res<BoogleRound>, board_words: Res<BoardWords>) {
+    let button_node = menu_button_node();
+    let words_node = Node {
+        width: Val::Px(300.0),
+        max_height: Val::Px(400.0),
+        margin: UiRect::all(Val::Px(10.0)),
+        overflow: Overflow::scroll_y(),
+        ..default()
+    };
+    let found_text = words_text("Found", round.found.iter().map(String::as_str), &round);
+    let missed_text = words_text(
+        "Missed",
+        board_words
+            .missed(&round)
+            .map(|solution| solution.word.as_str()),
+        &round,
+    );
+    let possible_points: u32 = board_words
+        .0
+        .iter()
+        .map(|solution| round.points(&solution.word))
+        .sum();
+    let score_text = format!(
+        "Words: {} of {}   Score: {} of {}",
+        round.found.len(),
+        board_words.0.len(),
+        round.score(),
+        possible_points
+    );
+    // the text does not block the scrolling of the list under it
+    let words_text_pickable = Pickable {
+        should_block_lower: false,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnEndScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Round over"),
+                        TextFont {
+                            font_size: 60.0,
+                            ..default()
+                        },
+                    ));
+                    parent.spawn(Text::new(score_text));
+                    parent.spawn((
+                        Node {
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        },
+                        children![
+                            (
+                                words_node.clone(),
+                                children![(Text::new(found_text), words_text_pickable.clone())]
+                            ),
+                            (
+                                words_node.clone(),
+                                children![(Text::new(missed_text), words_text_pickable)]
+                            ),
+                        ],
+                    ));
+                    parent.spawn((
+                        Button,
+                        button_node.clone(),
+                        BackgroundColor(NORMAL_BUTTON),
+                        EndScreenButtonAction::NewBoard,
+                        children![Text::new("New board")],
+                    ));
+                    parent.spawn((
+                        Button,
+                        button_node.clone(),
+                        BackgroundColor(NORMAL_BUTTON),
+                        EndScreenButtonAction::BackToMenu,
+                        children![Text::new("Back to menu")],
+                    ));
+                });
+        });
+}
+
+/// starts a round with a new board or goes back to the menu
+#[allow(clippy::type_complexity)]
+fn end_screen_action(
+    interaction_query: Query<
+        (&Interaction, &EndScreenButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut boogle_state: ResMut<NextState<BoogleState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match action {
+                EndScreenButtonAction::NewBoard => boogle_state.set(BoogleState::Loading),
+                EndScreenButtonAction::BackToMenu => {
+                    boogle_state.set(BoogleState::Disabled);
+                    game_state.set(GameState::Menu);
+                }
+            }
+        }
+    }
+}
@@ -1,14 +1,38 @@
-use bevy::prelude::*;
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
 use rand::prelude::*;
 
-use crate::{enums::GameState, functions::despawn_screen, menus::NORMAL_BUTTON};
+use crate::{
+    enums::GameState,
+    functions::despawn_screen,
+    games::boogle::{
+        board::{Board, Cell, PathError},
+        dictionary::{
+            CurrentDictionary, Dictionary, DictionaryPlugin, DictionarySettings, Language,
+            normalize_letter,
+        },
+        end_screen::EndScreenPlugin,
+        scoring::{ScoringPlugin, ScoringRules},
+        solver::{Solution, solve},
+    },
+    menus::{NORMAL_BUTTON, button_system, menu_button_node, scroll_system},
+};
 
 /// all items on game screen need this
 #[derive(Component)]
 struct OnGameScreen;
 /// all items on declaration screen need this
+#[allow(unused)]
 #[derive(Component)]
 struct OnDeclarationScreen;
+/// all items on the screen while the dictionary loads need this
+#[derive(Component)]
+struct OnLoadingScreen;
 
 const DICE1: [char; 6] = ['I', 'U', 'N', 'E', 'E', 'S'];
 const DICE2: [char; 6] = ['F', 'P', 'K', 'A', 'S', 'F'];
@@ -27,6 +51,20 @@ const DICE14: [char; 6] = ['U', 'I', 'C', 'T', 'M', 'O'];
 const DICE15: [char; 6] = ['E', 'E', 'N', 'H', 'G', 'W'];
 const DICE16: [char; 6] = ['N', 'A', 'E', 'A', 'G', 'E'];
 
+// round constanten
+/// seconds of one round
+const ROUND_TIME: f32 = 180.0;
+/// a word can use every die once
+const MAX_WORD_LENGTH: usize = 16;
+/// color of the dice on the path of the typed word
+const PATH_COLOR: Color = Color::srgb(0.9, 0.7, 0.2);
+/// a board with fewer words is rolled again
+const MIN_BOARD_WORDS: usize = 30;
+/// boards which are rolled at most, then the one with the most words is used
+const MAX_ROLLS: usize = 50;
+/// a hint shows this many dice of a missing word
+const HINT_LETTERS: usize = 2;
+
 const ALLDICES: [[char; 6]; 16] = [
     DICE1, DICE2, DICE3, DICE4, DICE5, DICE6, DICE7, DICE8, DICE9, DICE10, DICE11, DICE12, DICE13,
     DICE14, DICE15, DICE16,
@@ -38,33 +76,295 @@ pub enum BoogleState {
     #[default]
     Disabled,
     Declaration,
+    /// waits until the dictionary is loaded, words can only be checked with it
+    Loading,
     Game,
+    RoundOver,
+}
+
+/// time, typed word and found words of the running round
+#[derive(Resource)]
+pub struct BoogleRound {
+    pub timer: Timer,
+    /// the word while it is typed
+    pub input: String,
+    pub found: Vec<String>,
+    /// answer to the last entered word
+    pub message: String,
+    /// the first dice of a word the player asked for
+    pub hint: Vec<Cell>,
+    /// the scoring of the settings when the round started
+    pub scoring: ScoringRules,
+    /// language of the dictionary, it gives the letter values
+    pub language: Language,
+}
+
+impl Default for BoogleRound {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(ROUND_TIME, TimerMode::Once),
+            input: String::new(),
+            found: Vec::new(),
+            message: String::new(),
+            hint: Vec::new(),
+            scoring: ScoringRules::default(),
+            language: Language::default(),
+        }
+    }
+}
+
+impl BoogleRound {
+    /// a round with the scoring and the language of the settings
+    pub fn new(scoring: ScoringRules, language: Language) -> Self {
+        Self {
+            scoring,
+            language,
+            ..default()
+        }
+    }
+
+    /// points of a word with the scoring of the round
+    pub fn points(&self, word: &str) -> u32 {
+        self.scoring.points(word, self.language)
+    }
+
+    /// points of all found words
+    pub fn score(&self) -> u32 {
+        self.found.iter().map(|word| self.points(word)).sum()
+    }
+}
+
+/// all words of the dictionary on the board
+#[derive(Resource, Default)]
+pub struct BoardWords(pub Vec<Solution>);
+
+impl BoardWords {
+    /// the words on the board the player has not found
+    pub fn missed<'a>(&'a self, round: &'a BoogleRound) -> impl Iterator<Item = &'a Solution> {
+        self.0
+            .iter()
+            .filter(|solution| !round.found.contains(&solution.word))
+    }
+}
+
+/// reasons why an entered word does not count
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum WordRejection {
+    TooShort,
+    AlreadyFound,
+    NoPath(PathError),
+    NotAWord,
 }
 
+impl WordRejection {
+    pub fn message(self) -> String {
+        match self {
+            WordRejection::TooShort => "Too short".to_string(),
+            WordRejection::AlreadyFound => "Already found".to_string(),
+            WordRejection::NoPath(error) => error.message(),
+            WordRejection::NotAWord => "Not in the dictionary".to_string(),
+        }
+    }
+}
+
+/// a die on the board
+#[derive(Component)]
+struct Die(Cell);
+
+/// the text while the dictionary loads
+#[derive(Component)]
+struct LoadingText;
+
+/// the text with the remaining time
+#[derive(Component)]
+struct TimerText;
+
+/// the text with the typed word and the answer to the last word
+#[derive(Component)]
+struct InputText;
+
+/// the text with the found words
+#[derive(Component)]
+struct FoundWordsText;
+
+/// button which ends the round before the time is up
+#[derive(Component)]
+struct EndRoundButton;
+
+/// button which shows the start of a word the player has not found
+#[derive(Component)]
+struct HintButton;
+
 /// Boogle Plugin
 pub struct BooglePlugin;
 impl Plugin for BooglePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<BoogleState>()
+            .add_plugins((DictionaryPlugin, ScoringPlugin, EndScreenPlugin))
             .add_systems(OnEnter(GameState::Boogle), game_setup)
             //.add_systems(OnEnter(BoogleState::Declaration), ...)
             // .add_systems(
             //     OnExit(BoogleState::Declaration),
             //     despawn_screen::<OnDeclarationScreen>,
             // )
+            .add_systems(OnEnter(BoogleState::Loading), loading_setup)
+            .add_systems(
+                OnExit(BoogleState::Loading),
+                despawn_screen::<OnLoadingScreen>,
+            )
+            .add_systems(
+                Update,
+                wait_for_dictionary.run_if(in_state(BoogleState::Loading)),
+            )
             .add_systems(OnEnter(BoogleState::Game), boogle_setup)
-            .add_systems(OnExit(BoogleState::Game), despawn_screen::<OnGameScreen>);
+            .add_systems(OnExit(BoogleState::Game), despawn_screen::<OnGameScreen>)
+            .add_systems(
+                Update,
+                (
+                    round_timer,
+                    word_entry,
+                    end_round_button,
+                    hint_button,
+                    button_system,
+                    update_round_text,
+                    highlight_path,
+                )
+                    .chain()
+                    .run_if(in_state(BoogleState::Game)),
+            )
+            // the word lists of the round and of the end screen
+            .add_systems(Update, scroll_system.run_if(in_state(GameState::Boogle)));
     }
 }
 
 /// change the booglestate dissabled to game
 fn game_setup(mut boogle_state: ResMut<NextState<BoogleState>>) {
     // change to BoogleState::declaration
-    boogle_state.set(BoogleState::Game);
+    boogle_state.set(BoogleState::Loading);
 }
 
-/// setup for the boogle game surface
-fn boogle_setup(mut commands: Commands) {
+/// spawns the text which is shown until the dictionary is loaded
+fn loading_setup(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        OnLoadingScreen,
+        children![(
+            Text::new("Loading dictionary"),
+            TextFont {
+                font_size: 40.0,
+                ..default()
+            },
+            LoadingText,
+        )],
+    ));
+}
+
+/// starts the round when the dictionary is loaded
+/// if no dictionary can be loaded escape goes back to the menu
+fn wait_for_dictionary(
+    current_dictionary: Option<Res<CurrentDictionary>>,
+    dictionaries: Res<Assets<Dictionary>>,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut text: Single<&mut Text, With<LoadingText>>,
+    mut boogle_state: ResMut<NextState<BoogleState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let Some(current) = current_dictionary else {
+        return;
+    };
+    if dictionaries.contains(&current.0) {
+        boogle_state.set(BoogleState::Game);
+    } else if asset_server.load_state(&current.0).is_failed() {
+        text.0 = "Could not load the dictionary, press Escape".to_string();
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            boogle_state.set(BoogleState::Disabled);
+            game_state.set(GameState::Menu);
+        }
+    }
+}
+
+/// remaining time as minutes and seconds
+fn timer_text(timer: &Timer) -> String {
+    let seconds = timer.remaining_secs().ceil() as u32;
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+fn input_text(round: &BoogleRound) -> String {
+    format!("Word: {}_   {}", round.input, round.message)
+}
+
+/// the found words with their points, the newest on top
+fn found_words_text(round: &BoogleRound) -> String {
+    let mut text = format!("Words: {}   Score: {}\n", round.found.len(), round.score());
+    for word in round.found.iter().rev() {
+        text.push_str(&format!("\n{} ({})", word, round.points(word)));
+    }
+    text
+}
+
+/// checks the entered word against the round, the board and the dictionary
+/// returns the dice which spell the word
+pub fn check_word(
+    word: &str,
+    board: &Board,
+    dictionary: &Dictionary,
+    round: &BoogleRound,
+) -> Result<Vec<Cell>, WordRejection> {
+    if word.chars().count() < round.scoring.min_word_length {
+        return Err(WordRejection::TooShort);
+    }
+    if round.found.iter().any(|found| found == word) {
+        return Err(WordRejection::AlreadyFound);
+    }
+    let path = board.find_path(word).map_err(WordRejection::NoPath)?;
+    if !dictionary.contains(word) {
+        return Err(WordRejection::NotAWord);
+    }
+    Ok(path)
+}
+
+/// rolls boards until one has enough words of the dictionary with at least min_length letters
+fn roll_board(dictionary: &Dictionary, min_length: usize) -> (Board, Vec<Solution>) {
+    let board = Board::roll(&ALLDICES);
+    let words = solve(&board, dictionary.trie(), min_length);
+    let mut best = (board, words);
+    for _ in 1..MAX_ROLLS {
+        if best.1.len() >= MIN_BOARD_WORDS {
+            break;
+        }
+        let board = Board::roll(&ALLDICES);
+        let words = solve(&board, dictionary.trie(), min_length);
+        if words.len() > best.1.len() {
+            best = (board, words);
+        }
+    }
+    best
+}
+
+/// rolls a new board, starts the round and spawns the board with the timer,
+/// the word entry and the found words
+fn boogle_setup(
+    mut commands: Commands,
+    current_dictionary: Option<Res<CurrentDictionary>>,
+    dictionaries: Res<Assets<Dictionary>>,
+    scoring: Res<ScoringRules>,
+    dictionary_settings: Res<DictionarySettings>,
+    mut boogle_state: ResMut<NextState<BoogleState>>,
+) {
+    // the dictionary can only be missing if it was changed after loading
+    let Some(dictionary) = current_dictionary.and_then(|current| dictionaries.get(&current.0))
+    else {
+        boogle_state.set(BoogleState::Loading);
+        return;
+    };
+
     let dice_node = Node {
         width: Val::Px(75.0),
         height: Val::Px(75.0),
@@ -74,157 +374,240 @@ fn boogle_setup(mut commands: Commands) {
         ..default()
     };
 
-    let mut letters = Vec::new();
-    for dice in ALLDICES {
-        let letter = random_letter(&dice);
-        letters.push(letter);
-    }
-
-    let mut rng = rand::rng();
-    letters.shuffle(&mut rng);
+    let (board, words) = roll_board(dictionary, scoring.min_word_length);
+    let round = BoogleRound::new(*scoring, dictionary_settings.language);
 
     let letter_text_font = TextFont {
         font_size: 40.0,
         ..default()
     };
 
-    commands.spawn((
-        Node {
-            width: Val::Percent(100.0),
-            height: Val::Percent(100.0),
-            align_items: AlignItems::Center,
-            justify_content: JustifyContent::Center,
-            flex_direction: FlexDirection::Column,
-            ..default()
-        },
-        OnGameScreen,
-        children![
-            (
-                // first row
-                Node {
-                    flex_direction: FlexDirection::Row,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                children![
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[0].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[1].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[2].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[3].to_string()), letter_text_font.clone())]
-                    )
-                ]
-            ),
-            (
-                // second row
-                Node {
-                    flex_direction: FlexDirection::Row,
-                    align_items: AlignItems::Center,
-                    ..default()
-                },
-                children![
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[4].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[5].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[6].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[7].to_string()), letter_text_font.clone())]
-                    )
-                ]
-            ),
-            (
-                // third row
-                Node {
-                    flex_direction: FlexDirection::Row,
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Row,
+                ..default()
+            },
+            OnGameScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
                     align_items: AlignItems::Center,
                     ..default()
-                },
-                children![
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[8].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[9].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[10].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[11].to_string()), letter_text_font.clone())]
-                    )
-                ]
-            ),
-            (
-                // fourth row
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(timer_text(&round.timer)),
+                        letter_text_font.clone(),
+                        TimerText,
+                    ));
+                    for (row, letters) in board.rows().enumerate() {
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                for (column, letter) in letters.iter().enumerate() {
+                                    parent.spawn((
+                                        dice_node.clone(),
+                                        BackgroundColor(NORMAL_BUTTON),
+                                        Die(Cell::new(row, column)),
+                                        children![(
+                                            Text::new(letter.to_string()),
+                                            letter_text_font.clone()
+                                        )],
+                                    ));
+                                }
+                            });
+                    }
+                    parent.spawn((Text::new(input_text(&round)), InputText));
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Button,
+                                menu_button_node(),
+                                BackgroundColor(NORMAL_BUTTON),
+                                HintButton,
+                                children![Text::new("Hint")],
+                            ));
+                            parent.spawn((
+                                Button,
+                                menu_button_node(),
+                                BackgroundColor(NORMAL_BUTTON),
+                                EndRoundButton,
+                                children![Text::new("End round")],
+                            ));
+                        });
+                });
+            parent.spawn((
                 Node {
-                    flex_direction: FlexDirection::Row,
-                    align_items: AlignItems::Center,
+                    width: Val::Px(300.0),
+                    height: Val::Percent(80.0),
+                    margin: UiRect::left(Val::Px(40.0)),
+                    overflow: Overflow::scroll_y(),
                     ..default()
                 },
-                children![
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[12].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[13].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[14].to_string()), letter_text_font.clone())]
-                    ),
-                    (
-                        dice_node.clone(),
-                        BackgroundColor(NORMAL_BUTTON),
-                        children![(Text::new(letters[15].to_string()), letter_text_font.clone())]
-                    )
-                ]
-            ),
-        ],
-    ));
+                children![(
+                    Text::new(found_words_text(&round)),
+                    Pickable {
+                        should_block_lower: false,
+                        ..default()
+                    },
+                    FoundWordsText
+                )],
+            ));
+        });
+
+    commands.insert_resource(board);
+    commands.insert_resource(BoardWords(words));
+    commands.insert_resource(round);
+}
+
+/// the round is over when the time is up
+fn round_timer(
+    mut round: ResMut<BoogleRound>,
+    mut boogle_state: ResMut<NextState<BoogleState>>,
+    time: Res<Time>,
+) {
+    if round.timer.tick(time.delta()).just_finished() {
+        boogle_state.set(BoogleState::RoundOver);
+    }
+}
+
+/// typing changes the word, enter checks it and adds it to the found words
+/// umlauts and ß are typed as the letters of the dice
+fn word_entry(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut round: ResMut<BoogleRound>,
+    board: Res<Board>,
+    current_dictionary: Option<Res<CurrentDictionary>>,
+    dictionaries: Res<Assets<Dictionary>>,
+) {
+    let Some(dictionary) = current_dictionary.and_then(|current| dictionaries.get(&current.0))
+    else {
+        return;
+    };
+
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                round.hint.clear();
+                let word = std::mem::take(&mut round.input);
+                round.message = match check_word(&word, &board, dictionary, &round) {
+                    Ok(_) => {
+                        let message = format!("{} +{}", word, round.points(&word));
+                        round.found.push(word);
+                        message
+                    }
+                    Err(rejection) => format!("{}: {}", word, rejection.message()),
+                };
+            }
+            Key::Backspace => {
+                round.input.pop();
+            }
+            _ => {
+                if let Some(input) = &event.text {
+                    for letters in input.chars().filter_map(normalize_letter) {
+                        if round.input.len() + letters.len() <= MAX_WORD_LENGTH {
+                            round.input.push_str(letters);
+                        }
+                    }
+                }
+            }
+        }
+    }
 }
 
-/// give a random letter out of dice list
-fn random_letter(dice: &[char]) -> char {
-    let mut rng = rand::rng();
-    *dice.choose(&mut rng).expect("Arry is empty")
+/// ends the round before the time is up
+fn end_round_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<EndRoundButton>)>,
+    mut boogle_state: ResMut<NextState<BoogleState>>,
+) {
+    for interaction in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            boogle_state.set(BoogleState::RoundOver);
+        }
+    }
+}
+
+/// shows the first dice of a random word the player has not found
+fn hint_button(
+    interaction_query: Query<&Interaction, (Changed<Interaction>, With<HintButton>)>,
+    mut round: ResMut<BoogleRound>,
+    board_words: Res<BoardWords>,
+) {
+    for interaction in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let missed: Vec<&Solution> = board_words.missed(&round).collect();
+        let Some(solution) = missed.choose(&mut rand::rng()) else {
+            round.message = "No hint left".to_string();
+            continue;
+        };
+        let start: String = solution.word.chars().take(HINT_LETTERS).collect();
+        let length = solution.word.chars().count();
+        let hint = solution.path.iter().take(HINT_LETTERS).copied().collect();
+        round.message = format!("Hint: {}... ({} letters)", start, length);
+        round.hint = hint;
+    }
+}
+
+/// writes the time, the typed word and the found words into their texts
+#[allow(clippy::type_complexity)]
+fn update_round_text(
+    round: Res<BoogleRound>,
+    mut timer_text_query: Query<&mut Text, With<TimerText>>,
+    mut input_text_query: Query<&mut Text, (With<InputText>, Without<TimerText>)>,
+    mut found_text_query: Query<
+        &mut Text,
+        (With<FoundWordsText>, Without<TimerText>, Without<InputText>),
+    >,
+) {
+    for mut text in &mut timer_text_query {
+        text.0 = timer_text(&round.timer);
+    }
+    for mut text in &mut input_text_query {
+        text.0 = input_text(&round);
+    }
+    for mut text in &mut found_text_query {
+        text.0 = found_words_text(&round);
+    }
+}
+
+/// colors the dice on the path of the typed word, without a typed word the dice of the hint
+fn highlight_path(
+    round: Res<BoogleRound>,
+    board: Res<Board>,
+    mut dice: Query<(&Die, &mut BackgroundColor)>,
+) {
+    let path = if round.input.is_empty() {
+        round.hint.clone()
+    } else {
+        board.find_path(&round.input).unwrap_or_default()
+    };
+    for (Die(cell), mut background_color) in &mut dice {
+        *background_color = if path.contains(cell) {
+            PATH_COLOR.into()
+        } else {
+            NORMAL_BUTTON.into()
+        };
+    }
 }
@@ -0,0 +1,263 @@
+/// expands the words of a hunspell dictionary with the prefixes and suffixes of its .aff file
+/// only the affix rules are used, compound words are not built
+use std::collections::HashMap;
+
+/// how the flags after the '/' of a word are written
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum FlagType {
+    /// every character is a flag, also for FLAG UTF-8
+    Single,
+    /// two characters are one flag
+    Long,
+    /// numbers separated by commas
+    Numeric,
+}
+
+/// one character of the condition of an affix rule
+#[derive(Clone, Debug)]
+enum ConditionChar {
+    Any,
+    OneOf(Vec<char>),
+    NoneOf(Vec<char>),
+}
+
+impl ConditionChar {
+    fn matches(&self, letter: char) -> bool {
+        match self {
+            ConditionChar::Any => true,
+            ConditionChar::OneOf(letters) => letters.contains(&letter),
+            ConditionChar::NoneOf(letters) => !letters.contains(&letter),
+        }
+    }
+}
+
+/// reads a condition like "[^aeiou]y" into its characters
+fn parse_condition(text: &str) -> Vec<ConditionChar> {
+    let mut condition = Vec::new();
+    let mut chars = text.chars();
+    while let Some(letter) = chars.next() {
+        condition.push(match letter {
+            '.' => ConditionChar::Any,
+            '[' => {
+                let mut letters: Vec<char> = chars.by_ref().take_while(|&c| c != ']').collect();
+                if letters.first() == Some(&'^') {
+                    letters.remove(0);
+                    ConditionChar::NoneOf(letters)
+                } else {
+                    ConditionChar::OneOf(letters)
+                }
+            }
+            letter => ConditionChar::OneOf(vec![letter]),
+        });
+    }
+    condition
+}
+
+/// one line of a prefix or suffix class
+#[derive(Clone, Debug)]
+struct AffixRule {
+    strip: String,
+    add: String,
+    condition: Vec<ConditionChar>,
+}
+
+impl AffixRule {
+    fn parse(parts: &[&str]) -> Option<Self> {
+        let empty = |text: &str| {
+            if text == "0" {
+                String::new()
+            } else {
+                text.to_string()
+            }
+        };
+        // flags of twofold affixes after the '/' are not used
+        let add = parts.get(3).copied()?.split('/').next().unwrap_or_default();
+        Some(Self {
+            strip: empty(parts.get(2).copied()?),
+            add: empty(add),
+            condition: parse_condition(parts.get(4).copied().unwrap_or(".")),
+        })
+    }
+
+    /// the word with the prefix, none if the condition does not match the start
+    fn apply_prefix(&self, word: &str) -> Option<String> {
+        let letters: Vec<char> = word.chars().collect();
+        let matches = letters.len() >= self.condition.len()
+            && self
+                .condition
+                .iter()
+                .zip(&letters)
+                .all(|(condition, letter)| condition.matches(*letter));
+        let rest = word.strip_prefix(self.strip.as_str())?;
+        (matches && !rest.is_empty()).then(|| format!("{}{}", self.add, rest))
+    }
+
+    /// the word with the suffix, none if the condition does not match the end
+    fn apply_suffix(&self, word: &str) -> Option<String> {
+        let letters: Vec<char> = word.chars().collect();
+        let matches = letters.len() >= self.condition.len()
+            && self
+                .condition
+                .iter()
+                .rev()
+                .zip(letters.iter().rev())
+                .all(|(condition, letter)| condition.matches(*letter));
+        let rest = word.strip_suffix(self.strip.as_str())?;
+        (matches && !rest.is_empty()).then(|| format!("{}{}", rest, self.add))
+    }
+}
+
+/// all rules of one flag
+#[derive(Clone, Debug)]
+struct AffixClass {
+    /// prefixes and suffixes which both allow it can be combined
+    cross_product: bool,
+    rules: Vec<AffixRule>,
+}
+
+/// the parts of an .aff file which are needed to expand the words
+#[derive(Clone, Debug)]
+pub struct Affixes {
+    latin1: bool,
+    flag_type: FlagType,
+    prefixes: HashMap<String, AffixClass>,
+    suffixes: HashMap<String, AffixClass>,
+    /// words with this flag are only valid with an affix
+    need_affix: Option<String>,
+    /// words with this flag are only parts of compound words
+    only_in_compound: Option<String>,
+}
+
+impl Affixes {
+    /// reads the .aff file, the encoding is taken from its SET line
+    pub fn parse(bytes: &[u8]) -> Self {
+        let latin1 = String::from_utf8_lossy(bytes).lines().any(|line| {
+            let mut parts = line.split_whitespace();
+            parts.next() == Some("SET")
+                && parts
+                    .next()
+                    .is_some_and(|encoding| encoding.to_uppercase().starts_with("ISO8859"))
+        });
+        let mut affixes = Self {
+            latin1,
+            flag_type: FlagType::Single,
+            prefixes: HashMap::new(),
+            suffixes: HashMap::new(),
+            need_affix: None,
+            only_in_compound: None,
+        };
+
+        for line in affixes.decode(bytes).lines() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            match parts.as_slice() {
+                ["FLAG", "long", ..] => affixes.flag_type = FlagType::Long,
+                ["FLAG", "num", ..] => affixes.flag_type = FlagType::Numeric,
+                ["NEEDAFFIX", flag, ..] => affixes.need_affix = Some(flag.to_string()),
+                ["ONLYINCOMPOUND", flag, ..] => affixes.only_in_compound = Some(flag.to_string()),
+                [kind @ ("PFX" | "SFX"), flag, ..] => {
+                    let classes = if *kind == "PFX" {
+                        &mut affixes.prefixes
+                    } else {
+                        &mut affixes.suffixes
+                    };
+                    match classes.get_mut(*flag) {
+                        // the first line of a class is its header
+                        None => {
+                            let class = AffixClass {
+                                cross_product: parts.get(2) == Some(&"Y"),
+                                rules: Vec::new(),
+                            };
+                            classes.insert(flag.to_string(), class);
+                        }
+                        Some(class) => class.rules.extend(AffixRule::parse(&parts)),
+                    }
+                }
+                _ => {}
+            }
+        }
+        affixes
+    }
+
+    /// text of the .aff or .dic file in the encoding of the .aff file
+    pub fn decode(&self, bytes: &[u8]) -> String {
+        if self.latin1 {
+            bytes.iter().map(|&byte| char::from(byte)).collect()
+        } else {
+            String::from_utf8_lossy(bytes).into_owned()
+        }
+    }
+
+    fn parse_flags(&self, flags: &str) -> Vec<String> {
+        match self.flag_type {
+            FlagType::Single => flags.chars().map(String::from).collect(),
+            FlagType::Long => flags
+                .chars()
+                .collect::<Vec<_>>()
+                .chunks(2)
+                .map(|flag| flag.iter().collect())
+                .collect(),
+            FlagType::Numeric => flags
+                .split(',')
+                .map(|flag| flag.trim().to_string())
+                .collect(),
+        }
+    }
+
+    /// the word and all its forms with the affixes of its flags
+    pub fn expand_entry(&self, word: &str, flags: &str) -> Vec<String> {
+        let flags = self.parse_flags(flags);
+        let has_flag = |flag: &Option<String>| flag.as_ref().is_some_and(|f| flags.contains(f));
+        if has_flag(&self.only_in_compound) {
+            return Vec::new();
+        }
+
+        let mut words = Vec::new();
+        if !has_flag(&self.need_affix) {
+            words.push(word.to_string());
+        }
+
+        // suffixed forms which may also get a prefix
+        let mut cross_words = Vec::new();
+        for class in flags.iter().filter_map(|flag| self.suffixes.get(flag)) {
+            for suffixed in class
+                .rules
+                .iter()
+                .filter_map(|rule| rule.apply_suffix(word))
+            {
+                if class.cross_product {
+                    cross_words.push(suffixed.clone());
+                }
+                words.push(suffixed);
+            }
+        }
+        for class in flags.iter().filter_map(|flag| self.prefixes.get(flag)) {
+            for rule in &class.rules {
+                words.extend(rule.apply_prefix(word));
+                if class.cross_product {
+                    words.extend(cross_words.iter().filter_map(|w| rule.apply_prefix(w)));
+                }
+            }
+        }
+        words
+    }
+
+    /// all words of the .dic file with their forms
+    /// the first line with the number of words and the fields after the word are skipped
+    pub fn expand(&self, dic: &str) -> Vec<String> {
+        let mut lines = dic.lines().peekable();
+        if lines
+            .peek()
+            .is_some_and(|line| line.trim().parse::<usize>().is_ok())
+        {
+            lines.next();
+        }
+
+        lines
+            .filter_map(|line| line.split_whitespace().next())
+            .flat_map(|entry| {
+                let (word, flags) = entry.split_once('/').unwrap_or((entry, ""));
+                self.expand_entry(word, flags)
+            })
+            .collect()
+    }
+}
@@ -1 +1,11 @@
+pub mod board;
+pub mod dictionary;
+pub mod end_screen;
 pub mod game;
+pub mod hunspell;
+pub mod scoring;
+pub mod solver;
+pub mod trie;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,142 @@
+/// the points of the found words and the shortest word which counts
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::{ConfigError, load_config, save_config};
+use crate::games::boogle::dictionary::Language;
+
+// scoring constanten
+const SCORING_RULES_FILE: &str = "boogle_scoring.ron";
+/// the shortest word which counts without other settings
+const MIN_WORD_LENGTH: usize = 3;
+/// the minimum word length can be set up to this length
+const MAX_MIN_WORD_LENGTH: usize = 6;
+
+/// the ways to count the points of a word
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum ScoringScheme {
+    /// the official table by word length
+    #[default]
+    Classic,
+    /// the sum of the scrabble values of the letters in the language of the dictionary
+    LetterValues,
+}
+
+impl ScoringScheme {
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoringScheme::Classic => "Classic",
+            ScoringScheme::LetterValues => "Letters",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ScoringScheme::Classic => ScoringScheme::LetterValues,
+            ScoringScheme::LetterValues => ScoringScheme::Classic,
+        }
+    }
+}
+
+/// points of a word by the official table, longer words give more points
+pub fn classic_points(length: usize) -> u32 {
+    match length {
+        0..3 => 0,
+        3 | 4 => 1,
+        5 => 2,
+        6 => 3,
+        7 => 5,
+        _ => 11,
+    }
+}
+
+/// scrabble value of a letter, umlauts are written out so they count as two letters
+pub fn letter_value(letter: char, language: Language) -> u32 {
+    match language {
+        Language::German => match letter {
+            'A' | 'D' | 'E' | 'I' | 'N' | 'R' | 'S' | 'T' | 'U' => 1,
+            'G' | 'H' | 'L' | 'O' => 2,
+            'B' | 'M' | 'W' | 'Z' => 3,
+            'C' | 'F' | 'K' | 'P' => 4,
+            'J' | 'V' => 6,
+            'X' => 8,
+            'Q' | 'Y' => 10,
+            _ => 0,
+        },
+        Language::English => match letter {
+            'A' | 'E' | 'I' | 'L' | 'N' | 'O' | 'R' | 'S' | 'T' | 'U' => 1,
+            'D' | 'G' => 2,
+            'B' | 'C' | 'M' | 'P' => 3,
+            'F' | 'H' | 'V' | 'W' | 'Y' => 4,
+            'K' => 5,
+            'J' | 'X' => 8,
+            'Q' | 'Z' => 10,
+            _ => 0,
+        },
+    }
+}
+
+/// the scoring scheme and the minimum word length, saved in the config directory
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+pub struct ScoringRules {
+    pub scheme: ScoringScheme,
+    pub min_word_length: usize,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            scheme: ScoringScheme::default(),
+            min_word_length: MIN_WORD_LENGTH,
+        }
+    }
+}
+
+impl ScoringRules {
+    /// points of the word in the letters of the dice, words below the minimum length give none
+    pub fn points(&self, word: &str, language: Language) -> u32 {
+        let length = word.chars().count();
+        if length < self.min_word_length {
+            return 0;
+        }
+        match self.scheme {
+            ScoringScheme::Classic => classic_points(length),
+            ScoringScheme::LetterValues => word
+                .chars()
+                .map(|letter| letter_value(letter, language))
+                .sum(),
+        }
+    }
+
+    /// the next minimum word length, after the longest it starts again at the shortest
+    pub fn next_min_word_length(&mut self) {
+        self.min_word_length = if self.min_word_length >= MAX_MIN_WORD_LENGTH {
+            MIN_WORD_LENGTH
+        } else {
+            self.min_word_length + 1
+        };
+    }
+}
+
+/// plugin which loads the saved scoring rules
+pub struct ScoringPlugin;
+impl Plugin for ScoringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ScoringRules>()
+            .add_systems(Startup, load_scoring_rules);
+    }
+}
+
+/// reads the saved scoring rules, without a file the classic rules are used
+fn load_scoring_rules(mut commands: Commands) {
+    match load_config::<ScoringRules>(SCORING_RULES_FILE) {
+        Ok(Some(rules)) => commands.insert_resource(rules),
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// writes the scoring rules into the config directory
+pub fn save_scoring_rules(rules: &ScoringRules) -> Result<(), ConfigError> {
+    save_config(SCORING_RULES_FILE, rules)
+}
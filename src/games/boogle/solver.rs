@@ -0,0 +1,82 @@
+/// finds all words of a dictionary on a board
+use std::collections::HashMap;
+
+use crate::games::boogle::{
+    board::{Board, Cell},
+    trie::{Trie, TrieNode},
+};
+
+/// a word on the board with the dice which spell it
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Solution {
+    pub word: String,
+    pub path: Vec<Cell>,
+}
+
+/// the state of the search, the path and the word grow and shrink together
+struct Solver<'a> {
+    board: &'a Board,
+    trie: &'a Trie,
+    min_length: usize,
+    used: Vec<bool>,
+    path: Vec<Cell>,
+    word: String,
+    found: HashMap<String, Vec<Cell>>,
+}
+
+impl Solver<'_> {
+    /// goes on with the die if a word of the trie starts with the letters so far
+    fn visit(&mut self, cell: Cell, node: TrieNode) {
+        let index = cell.row * self.board.size() + cell.column;
+        if self.used[index] {
+            return;
+        }
+        let Some(letter) = self.board.get(cell) else {
+            return;
+        };
+        let Some(node) = self.trie.child(node, letter) else {
+            return;
+        };
+
+        self.used[index] = true;
+        self.path.push(cell);
+        self.word.push(letter);
+        if self.trie.is_word(node)
+            && self.path.len() >= self.min_length
+            && !self.found.contains_key(&self.word)
+        {
+            self.found.insert(self.word.clone(), self.path.clone());
+        }
+        for next in self.board.neighbours(cell) {
+            self.visit(next, node);
+        }
+        self.word.pop();
+        self.path.pop();
+        self.used[index] = false;
+    }
+}
+
+/// all words of the trie with at least min_length letters which are on the board,
+/// sorted alphabetically, a word which is on the board more than once has one of its paths
+pub fn solve(board: &Board, trie: &Trie, min_length: usize) -> Vec<Solution> {
+    let mut solver = Solver {
+        board,
+        trie,
+        min_length,
+        used: vec![false; board.size() * board.size()],
+        path: Vec::new(),
+        word: String::new(),
+        found: HashMap::new(),
+    };
+    for cell in board.cells() {
+        solver.visit(cell, Trie::ROOT);
+    }
+
+    let mut solutions: Vec<Solution> = solver
+        .found
+        .into_iter()
+        .map(|(word, path)| Solution { word, path })
+        .collect();
+    solutions.sort_unstable_by(|first, second| first.word.cmp(&second.word));
+    solutions
+}
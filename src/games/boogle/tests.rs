@@ -0,0 +1,286 @@
+/// tests of the boogle board, the dictionaries, the solver and the scoring
+use std::time::{Duration, Instant};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use crate::games::boogle::{
+    board::{Board, Cell, PathError},
+    dictionary::{Dictionary, Language, normalize_word},
+    game::{BoogleRound, WordRejection, check_word},
+    hunspell::Affixes,
+    scoring::{ScoringRules, ScoringScheme, classic_points},
+    solver::{Solution, solve},
+    trie::Trie,
+};
+
+/// number of words in the benchmark dictionary, a synthetic set of random words
+/// with the size of a real word list
+const BENCHMARK_WORDS: usize = 300_000;
+/// letters of the benchmark words, common letters more often
+const BENCHMARK_LETTERS: &str = "EEEEENNNNIIIIRRRSSSTTTAAADDHHULLGOOCMBFWKZPVJYXQ";
+
+fn board() -> Board {
+    Board::from_rows(&["CATS", "ODXE", "GQRN", "LMHU"]).expect("the rows form a square")
+}
+
+#[test]
+fn rows_must_form_a_square() {
+    assert!(Board::from_rows(&["AB", "C"]).is_none());
+    assert!(Board::from_rows(&[]).is_none());
+    assert_eq!(board().size(), 4);
+    assert_eq!(board().get(Cell::new(1, 2)), Some('X'));
+    assert_eq!(board().get(Cell::new(4, 0)), None);
+}
+
+#[test]
+fn words_follow_adjacent_dice() {
+    let path = board().find_path("cats").unwrap();
+    assert_eq!(
+        path,
+        vec![
+            Cell::new(0, 0),
+            Cell::new(0, 1),
+            Cell::new(0, 2),
+            Cell::new(0, 3)
+        ]
+    );
+
+    // diagonal steps count as adjacent
+    let path = board().find_path("COD").unwrap();
+    assert_eq!(
+        path,
+        vec![Cell::new(0, 0), Cell::new(1, 0), Cell::new(1, 1)]
+    );
+    assert!(path.windows(2).all(|pair| pair[0].is_adjacent(pair[1])));
+}
+
+#[test]
+fn dice_are_not_used_twice() {
+    assert_eq!(board().find_path("CAC"), Err(PathError::MissingLetter('C')));
+}
+
+#[test]
+fn invalid_words_have_a_reason() {
+    assert_eq!(board().find_path("CAB"), Err(PathError::MissingLetter('B')));
+    // all letters are on the board, but L is far away from C
+    assert_eq!(board().find_path("CL"), Err(PathError::NotConnected));
+}
+
+#[test]
+fn the_search_goes_back_from_dead_ends() {
+    // the first A leads nowhere, the second one reaches the N
+    let board = Board::from_rows(&["ABX", "XXX", "XAN"]).unwrap();
+    let path = board.find_path("AN").unwrap();
+    assert_eq!(path, vec![Cell::new(2, 1), Cell::new(2, 2)]);
+}
+
+#[test]
+fn umlauts_are_written_out() {
+    assert_eq!(normalize_word("Bäume"), Some("BAEUME".to_string()));
+    assert_eq!(normalize_word(" Straße "), Some("STRASSE".to_string()));
+    assert_eq!(normalize_word("Öl"), Some("OEL".to_string()));
+    // letters which are on no die
+    assert_eq!(normalize_word("café"), None);
+    assert_eq!(normalize_word("e-mail"), None);
+    assert_eq!(normalize_word(""), None);
+}
+
+#[test]
+fn dictionary_words_are_normalized() {
+    let dictionary = Dictionary::from_words(["Fuß", "Bär", "café"]);
+    assert!(dictionary.contains("FUSS"));
+    assert!(dictionary.contains("fuß"));
+    assert!(dictionary.contains("BAER"));
+    assert!(!dictionary.contains("CAFE"));
+}
+
+#[test]
+fn words_must_be_in_the_dictionary() {
+    let board = board();
+    let round = BoogleRound::default();
+    let dictionary = Dictionary::from_words(["cats"]);
+
+    assert!(check_word("CATS", &board, &dictionary, &round).is_ok());
+    assert_eq!(
+        check_word("COD", &board, &dictionary, &round),
+        Err(WordRejection::NotAWord)
+    );
+}
+
+#[test]
+fn hunspell_affixes_expand_the_words() {
+    let aff = "SET UTF-8
+PFX U Y 1
+PFX U 0 un .
+SFX S Y 2
+SFX S 0 s [^s]
+SFX S 0 es s
+SFX E N 1
+SFX E e en e
+";
+    let dic = "3\nbind/U\nglas/S\nkarte/ES\n";
+    let affixes = Affixes::parse(aff.as_bytes());
+    let mut words = affixes.expand(dic);
+    words.sort();
+    assert_eq!(
+        words,
+        [
+            "bind", "glas", "glases", "karte", "karten", "kartes", "unbind"
+        ]
+    );
+}
+
+#[test]
+fn hunspell_files_can_be_latin1() {
+    let affixes = Affixes::parse(b"SET ISO8859-1\n");
+    // "Bär" in ISO 8859-1
+    let words = affixes.expand(&affixes.decode(b"1\nB\xe4r\n"));
+    assert_eq!(words, ["Bär"]);
+}
+
+#[test]
+fn the_trie_knows_words_and_prefixes() {
+    let trie = Trie::new(["CAT", "CATS", "DOG"].map(String::from));
+    assert!(trie.contains("CAT"));
+    assert!(trie.contains("CATS"));
+    assert!(!trie.contains("CA"));
+    assert!(!trie.contains("DOGS"));
+    assert!(!trie.contains(""));
+
+    let prefix = trie
+        .child(Trie::ROOT, 'C')
+        .and_then(|node| trie.child(node, 'A'));
+    assert!(prefix.is_some_and(|node| !trie.is_word(node)));
+    assert_eq!(trie.child(Trie::ROOT, 'X'), None);
+}
+
+#[test]
+fn the_solver_finds_all_words_with_their_paths() {
+    let board = board();
+    let dictionary = Dictionary::from_words(["cats", "cat", "cod", "at", "cac", "lug", "dots"]);
+    let solutions = solve(&board, dictionary.trie(), 3);
+
+    let words: Vec<&str> = solutions
+        .iter()
+        .map(|solution| solution.word.as_str())
+        .collect();
+    // AT is too short, CAC uses a die twice, the letters of LUG are not connected
+    // and there is no T next to the O of DOTS
+    assert_eq!(words, ["CAT", "CATS", "COD"]);
+    for solution in &solutions {
+        assert_eq!(solution.path.len(), solution.word.len());
+        assert!(
+            solution
+                .path
+                .windows(2)
+                .all(|pair| pair[0].is_adjacent(pair[1]))
+        );
+        let letters: String = solution
+            .path
+            .iter()
+            .filter_map(|cell| board.get(*cell))
+            .collect();
+        assert_eq!(letters, solution.word);
+    }
+}
+
+/// random words of 3 to 10 letters
+fn benchmark_trie() -> Trie {
+    let letters: Vec<char> = BENCHMARK_LETTERS.chars().collect();
+    let mut rng = StdRng::seed_from_u64(0);
+    let words = (0..BENCHMARK_WORDS).map(|_| {
+        let length = rng.random_range(3..=10);
+        (0..length)
+            .map(|_| letters[rng.random_range(0..letters.len())])
+            .collect::<String>()
+    });
+    Trie::new(words)
+}
+
+/// the time of the fastest of a few solves, the first one fills the caches
+fn solve_time(board: &Board, trie: &Trie) -> (Vec<Solution>, Duration) {
+    (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            let solutions = solve(board, trie, 3);
+            (solutions, start.elapsed())
+        })
+        .min_by_key(|(_, time)| *time)
+        .expect("the board was solved")
+}
+
+/// the solver finds all words of a 4x4 and a 5x5 board in milliseconds
+/// run with `cargo test --release -- --ignored --nocapture solver_benchmark`
+#[test]
+#[ignore]
+fn solver_benchmark() {
+    let start = Instant::now();
+    let trie = benchmark_trie();
+    println!("trie of {} words: {:?}", BENCHMARK_WORDS, start.elapsed());
+
+    let boards = [
+        Board::from_rows(&["SERN", "TAIE", "NDRS", "EHLT"]).expect("the rows form a square"),
+        Board::from_rows(&["SERNE", "TAIEN", "NDRSI", "EHLTA", "GOREN"])
+            .expect("the rows form a square"),
+    ];
+    for board in &boards {
+        let (solutions, time) = solve_time(board, &trie);
+        println!(
+            "{0}x{0} board, {1} words: {2:?}",
+            board.size(),
+            solutions.len(),
+            time
+        );
+        assert!(!solutions.is_empty());
+        for solution in &solutions {
+            assert!(trie.contains(&solution.word));
+            assert!(board.find_path(&solution.word).is_ok());
+        }
+        assert!(time < Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn classic_points_follow_the_official_table() {
+    let points: Vec<u32> = (2..=10).map(classic_points).collect();
+    assert_eq!(points, [0, 1, 1, 2, 3, 5, 11, 11, 11]);
+}
+
+#[test]
+fn short_words_give_no_points() {
+    let rules = ScoringRules {
+        min_word_length: 4,
+        ..ScoringRules::default()
+    };
+    assert_eq!(rules.points("CAT", Language::German), 0);
+    assert_eq!(rules.points("CATS", Language::German), 1);
+
+    let round = BoogleRound::new(rules, Language::English);
+    assert_eq!(
+        check_word("CAT", &board(), &Dictionary::from_words(["cat"]), &round),
+        Err(WordRejection::TooShort)
+    );
+}
+
+#[test]
+fn letter_values_depend_on_the_language() {
+    let rules = ScoringRules {
+        scheme: ScoringScheme::LetterValues,
+        ..ScoringRules::default()
+    };
+    // Z is worth 3 in german and 10 in english scrabble
+    assert_eq!(rules.points("ZOO", Language::German), 7);
+    assert_eq!(rules.points("ZOO", Language::English), 12);
+}
+
+#[test]
+fn next_min_word_length_starts_again() {
+    let mut rules = ScoringRules::default();
+    let lengths: Vec<usize> = (0..5)
+        .map(|_| {
+            rules.next_min_word_length();
+            rules.min_word_length
+        })
+        .collect();
+    assert_eq!(lengths, [4, 5, 6, 3, 4]);
+}
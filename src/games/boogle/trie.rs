@@ -0,0 +1,98 @@
+/// position of a node in the trie
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct TrieNode(u32);
+
+/// a node with its children next to each other in the node list
+#[derive(Clone, Copy, Default, Debug)]
+struct Node {
+    /// bit n is set if there is a child for the n-th letter of the alphabet
+    children: u32,
+    /// index of the child with the first letter
+    first_child: u32,
+    /// the letters from the root to this node are a word
+    word: bool,
+}
+
+/// the words of a dictionary as a trie, so the solver can stop at prefixes of no word
+/// the words have to be normalized to the letters A to Z, all nodes are in one list
+#[derive(Clone, Debug)]
+pub struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Default for Trie {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+/// number of the letter in the alphabet, none for letters which are on no die
+fn letter_index(letter: char) -> Option<u8> {
+    letter.is_ascii_uppercase().then(|| letter as u8 - b'A')
+}
+
+impl Trie {
+    pub const ROOT: TrieNode = TrieNode(0);
+
+    /// words with other letters than A to Z are left out
+    pub fn new(words: impl IntoIterator<Item = String>) -> Self {
+        let mut words: Vec<Vec<u8>> = words
+            .into_iter()
+            .filter_map(|word| word.chars().map(letter_index).collect())
+            .filter(|word: &Vec<u8>| !word.is_empty())
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+
+        let mut trie = Self {
+            nodes: vec![Node::default()],
+        };
+        trie.build(0, &words, 0);
+        trie
+    }
+
+    /// adds the children of the node for the sorted words which all start with its letters
+    fn build(&mut self, node: usize, mut words: &[Vec<u8>], depth: usize) {
+        if words.first().is_some_and(|word| word.len() == depth) {
+            self.nodes[node].word = true;
+            words = &words[1..];
+        }
+
+        let groups: Vec<&[Vec<u8>]> = words
+            .chunk_by(|first, second| first[depth] == second[depth])
+            .collect();
+        let first_child = self.nodes.len();
+        self.nodes[node].first_child = first_child as u32;
+        for group in &groups {
+            self.nodes[node].children |= 1 << group[0][depth];
+        }
+        self.nodes
+            .resize(first_child + groups.len(), Node::default());
+        for (index, group) in groups.into_iter().enumerate() {
+            self.build(first_child + index, group, depth + 1);
+        }
+    }
+
+    /// the node after the letter, none if no word goes on with it
+    pub fn child(&self, node: TrieNode, letter: char) -> Option<TrieNode> {
+        let node = self.nodes[node.0 as usize];
+        let bit = 1 << letter_index(letter)?;
+        if node.children & bit == 0 {
+            return None;
+        }
+        Some(TrieNode(
+            node.first_child + (node.children & (bit - 1)).count_ones(),
+        ))
+    }
+
+    /// true if the letters to the node are a word
+    pub fn is_word(&self, node: TrieNode) -> bool {
+        self.nodes[node.0 as usize].word
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        word.chars()
+            .try_fold(Self::ROOT, |node, letter| self.child(node, letter))
+            .is_some_and(|node| self.is_word(node))
+    }
+}
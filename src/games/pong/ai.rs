@@ -0,0 +1,231 @@
+/// cpu opponent of the pong game
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::games::pong::{
+    game::{BALL_DIAMETER, Ball, GameSurface, Paddle, PaddleIntent, PongRng, Position, Velocity},
+    power_ups::Stuck,
+};
+
+/// how good the cpu plays
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Difficulty {
+    /// seconds between two decisions of the cpu
+    fn reaction_delay(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.4,
+            Difficulty::Medium => 0.2,
+            Difficulty::Hard => 0.05,
+        }
+    }
+
+    /// share of the paddle speed the cpu uses at most
+    fn max_speed(self) -> f32 {
+        match self {
+            Difficulty::Easy => 0.5,
+            Difficulty::Medium => 0.75,
+            Difficulty::Hard => 1.0,
+        }
+    }
+
+    /// maximal distance the predicted ball position is off
+    fn prediction_error(self) -> f32 {
+        match self {
+            Difficulty::Easy => 80.0,
+            Difficulty::Medium => 40.0,
+            Difficulty::Hard => 10.0,
+        }
+    }
+}
+
+/// who moves a paddle
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum Controller {
+    #[default]
+    Human,
+    Cpu(Difficulty),
+    /// a player on another machine in the local network
+    Remote,
+}
+
+/// the controller of every paddle
+#[derive(Resource, Serialize, Deserialize, Clone, Default, Debug)]
+pub struct PaddleControllers {
+    pub bottom: Controller,
+    pub left: Controller,
+    pub right: Controller,
+}
+
+impl PaddleControllers {
+    /// a versus game with the cpu on the side, the other paddle is moved by the player
+    pub fn against_cpu(side: CpuSide, difficulty: Difficulty) -> Self {
+        let cpu = Controller::Cpu(difficulty);
+        let (left, right) = match side {
+            CpuSide::Left => (cpu, Controller::Human),
+            CpuSide::Right => (Controller::Human, cpu),
+            CpuSide::Both => (cpu, cpu),
+        };
+        Self {
+            left,
+            right,
+            ..default()
+        }
+    }
+
+    /// controller of the paddle
+    pub fn get(&self, paddle: Paddle) -> Controller {
+        match paddle {
+            Paddle::Bottom => self.bottom,
+            Paddle::Left => self.left,
+            Paddle::Right => self.right,
+        }
+    }
+}
+
+/// the paddles the cpu moves in a versus game against the cpu
+#[derive(Resource, Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum CpuSide {
+    Left,
+    #[default]
+    Right,
+    /// the cpu plays against itself
+    Both,
+}
+
+impl CpuSide {
+    pub fn name(self) -> &'static str {
+        match self {
+            CpuSide::Left => "Left",
+            CpuSide::Right => "Right",
+            CpuSide::Both => "Both",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            CpuSide::Left => CpuSide::Right,
+            CpuSide::Right => CpuSide::Both,
+            CpuSide::Both => CpuSide::Left,
+        }
+    }
+}
+
+/// paddles moved by the cpu need this
+#[derive(Component)]
+pub struct CpuPlayer {
+    difficulty: Difficulty,
+    timer: Timer,
+    /// position on the movement axis the paddle moves to
+    target: f32,
+}
+
+impl CpuPlayer {
+    pub fn new(difficulty: Difficulty) -> Self {
+        Self {
+            difficulty,
+            timer: Timer::from_seconds(difficulty.reaction_delay(), TimerMode::Repeating),
+            target: 0.0,
+        }
+    }
+}
+
+/// sets the intent of the cpu paddles
+/// after every reaction delay the cpu predicts where the first ball reaches the paddle
+#[allow(clippy::type_complexity)]
+pub fn cpu_intent(
+    mut cpu_paddles: Query<(
+        &Position,
+        &Transform,
+        &Paddle,
+        &mut PaddleIntent,
+        &mut CpuPlayer,
+    )>,
+    balls: Query<(&Position, &Velocity), (With<Ball>, Without<Stuck>)>,
+    screen: Res<GameSurface>,
+    mut rng: ResMut<PongRng>,
+    time: Res<Time>,
+) {
+    let radius = BALL_DIAMETER / 2.0;
+
+    for (paddle_position, transform, paddle, mut intent, mut cpu) in &mut cpu_paddles {
+        let paddle_position = paddle_position.current;
+        // x is the movement axis of the paddle, y the axis to the paddle
+        let (position, line, range) = match paddle {
+            Paddle::Bottom => (
+                paddle_position.x,
+                paddle_position.y + transform.scale.y / 2.0 + radius,
+                (screen.left + radius, screen.right - radius),
+            ),
+            Paddle::Left => (
+                paddle_position.y,
+                paddle_position.x + transform.scale.x / 2.0 + radius,
+                (screen.bottom + radius, screen.top - radius),
+            ),
+            Paddle::Right => (
+                paddle_position.y,
+                paddle_position.x - transform.scale.x / 2.0 - radius,
+                (screen.bottom + radius, screen.top - radius),
+            ),
+        };
+        let paddle_axes = |vector: Vec2| match paddle {
+            Paddle::Bottom => vector,
+            Paddle::Left | Paddle::Right => vector.yx(),
+        };
+
+        if cpu.timer.tick(time.delta()).just_finished() {
+            let error: f32 = rng.random_range(-1.0..=1.0);
+            // without a ball on the way the paddle waits in the middle
+            let prediction = balls
+                .iter()
+                .filter_map(|(ball_position, ball_velocity)| {
+                    predict_ball(
+                        paddle_axes(ball_position.current),
+                        paddle_axes(ball_velocity.0),
+                        line,
+                        range,
+                    )
+                })
+                .min_by(|(time, _), (other, _)| time.total_cmp(other))
+                .map_or((range.0 + range.1) / 2.0, |(_, prediction)| prediction);
+            cpu.target = prediction + error * cpu.difficulty.prediction_error();
+        }
+
+        let direction = PaddleIntent::towards(cpu.target, position, time.delta_secs());
+        intent.0 = direction.0 * cpu.difficulty.max_speed();
+    }
+}
+
+/// time until the ball reaches the line on the y axis and its position on the x axis there
+/// bounces on the walls of the range are folded back into the range
+/// None if the ball flies away from the line
+pub fn predict_ball(
+    position: Vec2,
+    velocity: Vec2,
+    line: f32,
+    range: (f32, f32),
+) -> Option<(f32, f32)> {
+    let time = (line - position.y) / velocity.y;
+    if !time.is_finite() || time < 0.0 {
+        return None;
+    }
+
+    let (min, max) = range;
+    let span = max - min;
+    let unfolded = position.x + velocity.x * time;
+    if span <= 0.0 {
+        return Some((time, unfolded.clamp(min.min(max), max.max(min))));
+    }
+
+    let mut offset = (unfolded - min).rem_euclid(2.0 * span);
+    if offset > span {
+        offset = 2.0 * span - offset;
+    }
+    Some((time, min + offset))
+}
@@ -0,0 +1,144 @@
+/// bricks of the breakout mode
+use bevy::prelude::*;
+
+use crate::games::pong::{
+    game::{
+        Collider, GameSurface, OnGameScreen, PongMode, PongSimulation, PongState, pong_setup,
+        simulation_running,
+    },
+    level::CurrentLevel,
+    score::Score,
+};
+
+// brick constanten
+const BRICK_HEIGHT: f32 = 25.0;
+const BRICK_GAP: f32 = 5.0;
+const BRICK_TOP_OFFSET: f32 = 60.0;
+const BRICK_POINTS: u32 = 10;
+
+/// a brick which is destroyed after its hit points are gone
+#[derive(Component)]
+pub struct Brick {
+    hit_points: u32,
+}
+
+/// send when the ball hits a brick
+#[derive(Event)]
+pub struct BrickHit(pub Entity);
+
+/// number of the current breakout level
+#[derive(Resource, Default)]
+pub struct BrickLevel(pub usize);
+
+/// plugin for the breakout bricks
+pub struct BricksPlugin;
+impl Plugin for BricksPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<BrickHit>()
+            .init_resource::<BrickLevel>()
+            .add_systems(OnEnter(PongState::Loading), spawn_bricks.after(pong_setup))
+            .add_systems(
+                FixedUpdate,
+                (brick_hit, level_cleared)
+                    .chain()
+                    .after(PongSimulation)
+                    .run_if(simulation_running)
+                    .run_if(resource_equals(PongMode::Breakout)),
+            );
+    }
+}
+
+/// spawns the bricks of the current level
+fn spawn_bricks(
+    mut commands: Commands,
+    surface: Option<Res<GameSurface>>,
+    level: Res<CurrentLevel>,
+) {
+    let Some(surface) = surface else {
+        return;
+    };
+
+    let layout = &level.bricks;
+    let columns = layout.iter().map(|row| row.len()).max().unwrap_or(0);
+    if columns == 0 {
+        return;
+    }
+
+    let brick_width = (surface.right - surface.left - BRICK_GAP) / columns as f32 - BRICK_GAP;
+
+    for (row_index, row) in layout.iter().enumerate() {
+        for (column_index, cell) in row.chars().enumerate() {
+            let Some(hit_points) = cell.to_digit(10).filter(|hit_points| *hit_points > 0) else {
+                continue;
+            };
+
+            let x = surface.left
+                + BRICK_GAP
+                + brick_width / 2.0
+                + column_index as f32 * (brick_width + BRICK_GAP);
+            let y = surface.top
+                - BRICK_TOP_OFFSET
+                - BRICK_HEIGHT / 2.0
+                - row_index as f32 * (BRICK_HEIGHT + BRICK_GAP);
+
+            commands.spawn((
+                Sprite::from_color(brick_color(hit_points), Vec2::ONE),
+                Transform {
+                    translation: Vec3::new(x, y, 0.0),
+                    scale: Vec3::new(brick_width, BRICK_HEIGHT, 1.0),
+                    ..default()
+                },
+                Brick { hit_points },
+                Collider,
+                OnGameScreen,
+            ));
+        }
+    }
+}
+
+/// removes a hit point of every hit brick and gives points
+/// bricks without hit points get despawned
+fn brick_hit(
+    mut commands: Commands,
+    mut brick_hits: EventReader<BrickHit>,
+    mut bricks: Query<(&mut Brick, &mut Sprite)>,
+    mut score: ResMut<Score>,
+) {
+    for BrickHit(entity) in brick_hits.read() {
+        let Ok((mut brick, mut sprite)) = bricks.get_mut(*entity) else {
+            continue;
+        };
+        if brick.hit_points == 0 {
+            continue;
+        }
+
+        score.points += BRICK_POINTS;
+        brick.hit_points -= 1;
+        if brick.hit_points == 0 {
+            commands.entity(*entity).despawn();
+        } else {
+            sprite.color = brick_color(brick.hit_points);
+        }
+    }
+}
+
+/// if no bricks are left the next level gets loaded
+fn level_cleared(
+    bricks: Query<(), With<Brick>>,
+    mut level: ResMut<BrickLevel>,
+    mut pong_state: ResMut<NextState<PongState>>,
+) {
+    if bricks.is_empty() {
+        level.0 += 1;
+        pong_state.set(PongState::Loading);
+    }
+}
+
+/// color of a brick depending on its hit points
+fn brick_color(hit_points: u32) -> Color {
+    match hit_points {
+        1 => Color::srgb(0.2, 0.8, 0.3),
+        2 => Color::srgb(0.9, 0.8, 0.2),
+        _ => Color::srgb(0.9, 0.4, 0.1),
+    }
+}
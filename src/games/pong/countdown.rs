@@ -0,0 +1,159 @@
+/// the countdown before the ball is served
+use bevy::prelude::*;
+
+use crate::functions::despawn_screen;
+use crate::games::pong::{
+    game::{Ball, OnGameScreen, PongState, Position, Velocity},
+    network::is_network_client,
+    replay::ReplayPlayer,
+};
+
+// countdown constanten
+/// seconds from loading until the ball is served
+const COUNTDOWN: f32 = 3.0;
+/// seconds the go text is shown after the serve
+const GO_DURATION: f32 = 0.5;
+const SERVE_INDICATOR_LENGTH: f32 = 80.0;
+const SERVE_INDICATOR_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+
+#[derive(Resource, Deref, DerefMut)]
+/// timer vor the game
+struct GameTimer(Timer);
+
+/// all elements of the countdown need this
+#[derive(Component)]
+struct OnCountdownScreen;
+
+/// the text with the seconds until the serve
+#[derive(Component)]
+struct CountdownText;
+
+/// the go text after the serve, removed when its timer is finished
+#[derive(Component, Deref, DerefMut)]
+struct GoText(Timer);
+
+/// plugin for the countdown before the serve
+pub struct CountdownPlugin;
+impl Plugin for CountdownPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(PongState::Loading), countdown_setup)
+            .add_systems(
+                OnExit(PongState::Loading),
+                despawn_screen::<OnCountdownScreen>,
+            )
+            // only after the serve, not when the game goes on after the pause menu
+            .add_systems(
+                OnTransition {
+                    exited: PongState::Loading,
+                    entered: PongState::Game,
+                },
+                go_setup,
+            )
+            .add_systems(
+                Update,
+                (
+                    countdown,
+                    launch_key
+                        .run_if(not(resource_exists::<ReplayPlayer>))
+                        .run_if(not(is_network_client)),
+                    serve_indicator,
+                )
+                    .run_if(in_state(PongState::Loading)),
+            )
+            .add_systems(Update, hide_go.run_if(in_state(PongState::Game)));
+    }
+}
+
+/// starts the timer and spawns the countdown text
+fn countdown_setup(mut commands: Commands) {
+    commands.insert_resource(GameTimer(Timer::from_seconds(COUNTDOWN, TimerMode::Once)));
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        OnCountdownScreen,
+        children![(
+            Text::new(countdown_text(COUNTDOWN)),
+            TextFont {
+                font_size: 80.0,
+                ..default()
+            },
+            CountdownText,
+        )],
+    ));
+}
+
+/// wait the countdown bevor the game starts
+/// switch from PongState::Loading to PongState::Game
+fn countdown(
+    mut game_state: ResMut<NextState<PongState>>,
+    time: Res<Time>,
+    mut timer: ResMut<GameTimer>,
+    mut text: Single<&mut Text, With<CountdownText>>,
+) {
+    if timer.tick(time.delta()).finished() {
+        game_state.set(PongState::Game);
+    }
+    text.0 = countdown_text(timer.remaining_secs());
+}
+
+/// whole seconds left of a countdown
+pub fn countdown_text(remaining: f32) -> String {
+    format!("{}", remaining.ceil().max(1.0))
+}
+
+/// space serves the ball without waiting for the countdown
+/// replays use space for the playback, there the countdown always runs
+/// a network client starts with the host, so only the host can serve early
+fn launch_key(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut game_state: ResMut<NextState<PongState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        game_state.set(PongState::Game);
+    }
+}
+
+/// arrow from the ball in the direction it is served
+fn serve_indicator(mut gizmos: Gizmos, balls: Query<(&Position, &Velocity), With<Ball>>) {
+    for (position, velocity) in &balls {
+        let start = position.current;
+        let end = start + velocity.normalize_or_zero() * SERVE_INDICATOR_LENGTH;
+        gizmos.arrow_2d(start, end, SERVE_INDICATOR_COLOR);
+    }
+}
+
+/// shows go when the ball is served
+fn go_setup(mut commands: Commands) {
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        GoText(Timer::from_seconds(GO_DURATION, TimerMode::Once)),
+        OnGameScreen,
+        children![(
+            Text::new("Go!"),
+            TextFont {
+                font_size: 80.0,
+                ..default()
+            },
+        )],
+    ));
+}
+
+/// removes the go text after a short time
+fn hide_go(mut commands: Commands, mut go_texts: Query<(Entity, &mut GoText)>, time: Res<Time>) {
+    for (entity, mut timer) in &mut go_texts {
+        if timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
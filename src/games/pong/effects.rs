@@ -0,0 +1,309 @@
+/// optional effects of the pong game, they only change what is drawn
+/// and never the simulation, so replays stay the same
+use bevy::prelude::*;
+use rand::random_range;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::{ConfigError, load_config, save_config};
+use crate::games::pong::game::{
+    BALL_DIAMETER, Ball, BallLost, ObjectHit, OnGameScreen, Paddle, PaddleHit, PongState,
+};
+
+// effect constanten
+const EFFECT_SETTINGS_FILE: &str = "effects.ron";
+/// seconds a part of the trail is visible
+const TRAIL_LIFETIME: f32 = 0.15;
+const TRAIL_COLOR: Color = Color::srgba(1.0, 0.0, 0.0, 0.3);
+const PARTICLES_PER_HIT: usize = 12;
+const PARTICLE_SIZE: f32 = 6.0;
+const PARTICLE_SPEED: f32 = 250.0;
+const PARTICLE_LIFETIME: f32 = 0.4;
+const PARTICLE_COLOR: Color = Color::srgb(1.0, 0.8, 0.2);
+const SHAKE_DURATION: f32 = 0.3;
+/// biggest distance of the camera from the center while it shakes
+const SHAKE_STRENGTH: f32 = 12.0;
+const SQUASH_DURATION: f32 = 0.15;
+/// the paddle gets this much thinner and longer when the ball hits it
+const SQUASH_AMOUNT: f32 = 0.3;
+
+/// the effects a player can switch off
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Effect {
+    Trail,
+    Particles,
+    ScreenShake,
+    Squash,
+}
+
+impl Effect {
+    pub const ALL: [Effect; 4] = [
+        Effect::Trail,
+        Effect::Particles,
+        Effect::ScreenShake,
+        Effect::Squash,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Effect::Trail => "Trail",
+            Effect::Particles => "Particles",
+            Effect::ScreenShake => "Shake",
+            Effect::Squash => "Squash",
+        }
+    }
+}
+
+/// the enabled effects, saved in the config directory
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct EffectSettings {
+    pub trail: bool,
+    pub particles: bool,
+    pub screen_shake: bool,
+    pub squash: bool,
+}
+
+impl Default for EffectSettings {
+    fn default() -> Self {
+        Self {
+            trail: true,
+            particles: true,
+            screen_shake: true,
+            squash: true,
+        }
+    }
+}
+
+impl EffectSettings {
+    pub fn enabled(&self, effect: Effect) -> bool {
+        match effect {
+            Effect::Trail => self.trail,
+            Effect::Particles => self.particles,
+            Effect::ScreenShake => self.screen_shake,
+            Effect::Squash => self.squash,
+        }
+    }
+
+    pub fn toggle(&mut self, effect: Effect) {
+        let enabled = match effect {
+            Effect::Trail => &mut self.trail,
+            Effect::Particles => &mut self.particles,
+            Effect::ScreenShake => &mut self.screen_shake,
+            Effect::Squash => &mut self.squash,
+        };
+        *enabled = !*enabled;
+    }
+}
+
+/// material of the trail behind the balls
+#[derive(Resource)]
+struct TrailMaterial(Handle<ColorMaterial>);
+
+/// a part of the trail, it shrinks until its timer is finished
+#[derive(Component, Deref, DerefMut)]
+struct TrailPart(Timer);
+
+/// a particle of a hit, it flies away and fades out
+#[derive(Component)]
+struct Particle {
+    velocity: Vec2,
+    timer: Timer,
+}
+
+/// the camera shakes until the timer is finished
+#[derive(Resource, Deref, DerefMut)]
+struct ScreenShake(Timer);
+
+/// the paddle is squashed until the timer is finished
+#[derive(Component, Deref, DerefMut)]
+struct Squash(Timer);
+
+/// plugin for the effects
+pub struct EffectsPlugin;
+impl Plugin for EffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EffectSettings>()
+            .add_systems(Startup, (load_effect_settings, effects_setup))
+            .add_systems(
+                Update,
+                (
+                    spawn_trail.run_if(effect_enabled(Effect::Trail)),
+                    spawn_particles.run_if(effect_enabled(Effect::Particles)),
+                    start_screen_shake.run_if(effect_enabled(Effect::ScreenShake)),
+                    start_squash.run_if(effect_enabled(Effect::Squash)),
+                    update_trail,
+                    update_particles,
+                    update_squash,
+                )
+                    .run_if(in_state(PongState::Game)),
+            )
+            .add_systems(Update, shake_camera.run_if(resource_exists::<ScreenShake>));
+    }
+}
+
+/// run condition for the systems which start an effect
+fn effect_enabled(effect: Effect) -> impl Fn(Res<EffectSettings>) -> bool {
+    move |settings: Res<EffectSettings>| settings.enabled(effect)
+}
+
+/// reads the saved effect settings, without a file all effects are enabled
+fn load_effect_settings(mut commands: Commands) {
+    match load_config::<EffectSettings>(EFFECT_SETTINGS_FILE) {
+        Ok(Some(settings)) => commands.insert_resource(settings),
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// writes the effect settings into the config directory
+pub fn save_effect_settings(settings: &EffectSettings) -> Result<(), ConfigError> {
+    save_config(EFFECT_SETTINGS_FILE, settings)
+}
+
+/// the material is shared by all parts of the trail
+fn effects_setup(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands.insert_resource(TrailMaterial(materials.add(TRAIL_COLOR)));
+}
+
+/// leaves a part of the trail behind every moving ball
+fn spawn_trail(
+    mut commands: Commands,
+    balls: Query<(&Transform, &Mesh2d), With<Ball>>,
+    material: Res<TrailMaterial>,
+    time: Res<Time>,
+) {
+    // a paused replay leaves no trail
+    if time.delta_secs() == 0.0 {
+        return;
+    }
+    for (transform, mesh) in &balls {
+        commands.spawn((
+            mesh.clone(),
+            MeshMaterial2d(material.0.clone()),
+            Transform {
+                translation: transform.translation.with_z(-0.5),
+                ..*transform
+            },
+            TrailPart(Timer::from_seconds(TRAIL_LIFETIME, TimerMode::Once)),
+            OnGameScreen,
+        ));
+    }
+}
+
+/// shrinks the trail and removes the old parts
+fn update_trail(
+    mut commands: Commands,
+    mut parts: Query<(Entity, &mut Transform, &mut TrailPart)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut timer) in &mut parts {
+        if timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.scale = Vec2::splat(BALL_DIAMETER * timer.fraction_remaining()).extend(1.0);
+    }
+}
+
+/// a burst of particles where a ball bounces
+fn spawn_particles(
+    mut commands: Commands,
+    mut object_hits: EventReader<ObjectHit>,
+    mut paddle_hits: EventReader<PaddleHit>,
+) {
+    let positions = object_hits
+        .read()
+        .map(|hit| hit.position)
+        .chain(paddle_hits.read().map(|hit| hit.position));
+    for position in positions {
+        for _ in 0..PARTICLES_PER_HIT {
+            // the look of the particles does not need the seeded rng of the game
+            let direction = Vec2::from_angle(random_range(0.0..std::f32::consts::TAU));
+            commands.spawn((
+                Sprite::from_color(PARTICLE_COLOR, Vec2::splat(PARTICLE_SIZE)),
+                Transform::from_translation(position.extend(0.5)),
+                Particle {
+                    velocity: direction * PARTICLE_SPEED * random_range(0.5..1.0),
+                    timer: Timer::from_seconds(PARTICLE_LIFETIME, TimerMode::Once),
+                },
+                OnGameScreen,
+            ));
+        }
+    }
+}
+
+/// moves and fades the particles
+fn update_particles(
+    mut commands: Commands,
+    mut particles: Query<(Entity, &mut Transform, &mut Sprite, &mut Particle)>,
+    time: Res<Time>,
+) {
+    for (entity, mut transform, mut sprite, mut particle) in &mut particles {
+        if particle.timer.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        transform.translation += (particle.velocity * time.delta_secs()).extend(0.0);
+        sprite.color.set_alpha(particle.timer.fraction_remaining());
+    }
+}
+
+/// a lost ball shakes the camera
+fn start_screen_shake(mut commands: Commands, mut ball_lost: EventReader<BallLost>) {
+    if ball_lost.read().count() > 0 {
+        commands.insert_resource(ScreenShake(Timer::from_seconds(
+            SHAKE_DURATION,
+            TimerMode::Once,
+        )));
+    }
+}
+
+/// moves the camera around the center, weaker until the shake is over
+/// the real time is used, so the shake also ends on the end screen
+fn shake_camera(
+    mut commands: Commands,
+    mut shake: ResMut<ScreenShake>,
+    mut camera: Single<&mut Transform, With<Camera2d>>,
+    time: Res<Time<Real>>,
+) {
+    if shake.tick(time.delta()).finished() {
+        camera.translation = Vec3::ZERO.with_z(camera.translation.z);
+        commands.remove_resource::<ScreenShake>();
+        return;
+    }
+    let offset = Vec2::from_angle(random_range(0.0..std::f32::consts::TAU))
+        * SHAKE_STRENGTH
+        * shake.fraction_remaining();
+    camera.translation = offset.extend(camera.translation.z);
+}
+
+/// a hit paddle gets squashed
+fn start_squash(mut commands: Commands, mut paddle_hits: EventReader<PaddleHit>) {
+    for hit in paddle_hits.read() {
+        commands
+            .entity(hit.paddle)
+            .try_insert(Squash(Timer::from_seconds(
+                SQUASH_DURATION,
+                TimerMode::Once,
+            )));
+    }
+}
+
+/// squashes the sprite along the normal of the paddle and stretches it along its axis
+/// only the sprite changes, the collider is the transform of the paddle
+fn update_squash(
+    mut commands: Commands,
+    mut paddles: Query<(Entity, &Paddle, &mut Sprite, &mut Squash)>,
+    time: Res<Time>,
+) {
+    for (entity, paddle, mut sprite, mut squash) in &mut paddles {
+        if squash.tick(time.delta()).finished() {
+            sprite.custom_size = Some(Vec2::ONE);
+            commands.entity(entity).remove::<Squash>();
+            continue;
+        }
+        let amount = SQUASH_AMOUNT * squash.fraction_remaining();
+        let normal = paddle.normal().abs();
+        let axis = Vec2::ONE - normal;
+        sprite.custom_size = Some(Vec2::ONE + axis * amount - normal * amount);
+    }
+}
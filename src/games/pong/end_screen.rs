@@ -0,0 +1,157 @@
+/// the screen after the pong game is over
+use bevy::prelude::*;
+
+use crate::enums::GameState;
+use crate::functions::despawn_screen;
+use crate::games::pong::{
+    game::{PongMode, PongState, Wall, new_game},
+    highscore::{
+        ChangedRules, DEFAULT_RULES_HINT, NameEntryText, PendingHighScore, name_entry_text,
+    },
+    replay::ReplayPlayer,
+    rules::MatchRules,
+    score::Score,
+    versus::{VersusScore, versus_score_text},
+};
+use crate::menus::{NORMAL_BUTTON, button_system, menu_button_node};
+
+/// all elements on the end screen need this
+#[derive(Component)]
+struct OnEndScreen;
+
+/// button actions of the end screen
+#[derive(Component)]
+enum EndScreenButtonAction {
+    PlayAgain,
+    BackToMenu,
+}
+
+/// plugin for the end screen
+pub struct EndScreenPlugin;
+impl Plugin for EndScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(PongState::GameOver), end_screen_setup)
+            .add_systems(OnExit(PongState::GameOver), despawn_screen::<OnEndScreen>)
+            .add_systems(
+                Update,
+                (button_system, end_screen_action).run_if(in_state(PongState::GameOver)),
+            );
+    }
+}
+
+/// spawns the final score, the survived time and the buttons
+/// in the versus mode the winner and the goals are shown
+/// a new high score asks for the name of the player
+/// games with changed rules say that they do not count for the high scores
+#[allow(clippy::too_many_arguments)]
+pub fn end_screen_setup(
+    mut commands: Commands,
+    score: Res<Score>,
+    versus_score: Res<VersusScore>,
+    mode: Res<PongMode>,
+    rules: Res<MatchRules>,
+    pending: Option<Res<PendingHighScore>>,
+    changed_rules: Option<Res<ChangedRules>>,
+) {
+    let button_node = menu_button_node();
+
+    let (title, result) = match *mode {
+        PongMode::Versus => {
+            let title = match versus_score.winner(rules.points_to_win) {
+                Some(Wall::Left) => "Left player wins",
+                Some(_) => "Right player wins",
+                None => "Game over",
+            };
+            (title, versus_score_text(&versus_score))
+        }
+        PongMode::Classic | PongMode::Breakout => ("Game over", format!("Score: {}", score.points)),
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnEndScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(title),
+                        TextFont {
+                            font_size: 60.0,
+                            ..default()
+                        },
+                    ));
+                    parent.spawn(Text::new(result));
+                    parent.spawn(Text::new(format!("Time: {:.1} s", score.time)));
+                    if let Some(pending) = pending {
+                        parent.spawn((
+                            Text::new("New high score!"),
+                            TextFont {
+                                font_size: 40.0,
+                                ..default()
+                            },
+                        ));
+                        parent.spawn((Text::new(name_entry_text(&pending.name)), NameEntryText));
+                    } else if changed_rules.is_some() {
+                        parent.spawn(Text::new(DEFAULT_RULES_HINT));
+                    }
+                    parent.spawn((
+                        Button,
+                        button_node.clone(),
+                        BackgroundColor(NORMAL_BUTTON),
+                        EndScreenButtonAction::PlayAgain,
+                        children![Text::new("Play again")],
+                    ));
+                    parent.spawn((
+                        Button,
+                        button_node.clone(),
+                        BackgroundColor(NORMAL_BUTTON),
+                        EndScreenButtonAction::BackToMenu,
+                        children![Text::new("Back to menu")],
+                    ));
+                });
+        });
+}
+
+/// restarts the game or goes back to the menu
+#[allow(clippy::type_complexity)]
+fn end_screen_action(
+    interaction_query: Query<
+        (&Interaction, &EndScreenButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut commands: Commands,
+    mut pong_state: ResMut<NextState<PongState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut player: Option<ResMut<ReplayPlayer>>,
+    rules: Res<MatchRules>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match action {
+                EndScreenButtonAction::PlayAgain => new_game(
+                    &mut commands,
+                    &mut pong_state,
+                    player.as_deref_mut(),
+                    &rules,
+                ),
+                EndScreenButtonAction::BackToMenu => {
+                    pong_state.set(PongState::Disabled);
+                    game_state.set(GameState::Menu);
+                }
+            }
+        }
+    }
+}
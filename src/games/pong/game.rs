@@ -1,136 +1,434 @@
 /// the whole logic of the pong game
 use bevy::{
-    math::bounding::{Aabb2d, BoundingCircle, IntersectsVolume},
+    math::bounding::{Aabb2d, BoundingVolume},
     prelude::*,
 };
+use rand::{SeedableRng, rngs::StdRng};
+use serde::{Deserialize, Serialize};
 
 use crate::enums::GameState;
 use crate::functions::despawn_screen;
+use crate::games::pong::{
+    ai::{Controller, CpuPlayer, PaddleControllers, cpu_intent},
+    bricks::{Brick, BrickHit, BrickLevel, BricksPlugin},
+    countdown::CountdownPlugin,
+    effects::EffectsPlugin,
+    end_screen::EndScreenPlugin,
+    highscore::HighScorePlugin,
+    input::{InputPlugin, player_intent},
+    level::{CurrentLevel, LevelPlugin, PongLevel, PongLevels, select_level},
+    network::{NetworkPlugin, RemotePlayer, is_network_client},
+    pause_menu::PausePlugin,
+    physics::{Hit, reflect, sweep_circle_aabb},
+    power_ups::{ActivePowerUps, PowerUp, PowerUpPlugin, Stuck, ball_time_scale},
+    replay::{ReplayPlayer, ReplayPlugin, ReplayRecorder, record_intent, replay_intent},
+    rules::{MatchRules, RulesPlugin},
+    score::{POINTS_PER_HIT, Score, ScorePlugin},
+    sound::SoundPlugin,
+    versus::{VersusPlugin, VersusScore},
+};
 
 // paddle constanten
-const PADDLE_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
-const PADDLE_SIZE: Vec2 = Vec2::new(100.0, 30.0);
-const PADDLE_SPEED: f32 = 500.0;
+pub const PADDLE_COLOR: Color = Color::srgb(1.0, 1.0, 1.0);
+pub const PADDLE_SIZE: Vec2 = Vec2::new(100.0, 30.0);
+pub const PADDLE_SPEED: f32 = 500.0;
+const PADDLE_WALL_DISTANCE: f32 = 100.0;
+/// biggest angle between the ball and the paddle normal after a bounce
+pub const MAX_BOUNCE_ANGLE: f32 = 60.0_f32.to_radians();
+/// extra angle when the paddle moves while the ball hits it
+const PADDLE_ENGLISH_ANGLE: f32 = 15.0_f32.to_radians();
+/// hits with a normal closer to the paddle normal than this are on the face of the paddle
+const PADDLE_FACE_THRESHOLD: f32 = 0.9;
 
 // ball constanten
 const BALL_COLOR: Color = Color::srgb(1.0, 0.0, 0.0);
-const BALL_DIAMETER: f32 = 30.0;
-const BALL_SPEED: f32 = 500.0;
+pub const BALL_DIAMETER: f32 = 30.0;
+pub const BALL_SPEED: f32 = 500.0;
 const BALL_SPAWN_POINT: Vec3 = Vec3::new(0.0, 50.0, 0.0);
 const INITIAL_BALL_DIRECTION: Vec2 = Vec2::new(0.5, 0.5);
+/// the ball gets faster with every paddle hit
+pub const BALL_SPEED_UP: f32 = 1.05;
+/// the ball gets at most this times faster than the level speed
+pub const MAX_BALL_SPEED_FACTOR: f32 = 2.0;
+/// the ball can bounce this often in one simulation step
+pub const MAX_BOUNCES_PER_STEP: usize = 4;
+
+// simulation constanten
+/// simulation steps per second, independent of the framerate
+pub const SIMULATION_HZ: f64 = 120.0;
+
+// arena constanten
+/// logical size of the game surface, the camera scales it to fit into the window
+pub const ARENA_SIZE: Vec2 = Vec2::new(1280.0, 720.0);
+/// the arena is darker than the bars beside it in windows with another aspect ratio
+const ARENA_COLOR: Color = Color::srgb(0.0, 0.0, 0.0);
 
 // wall constanten
 const WALL_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
 
+/// paddle and the wall it defends
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub enum Paddle {
+    Bottom,
+    Left,
+    Right,
+}
+
+impl Paddle {
+    /// normal of the side of the paddle the ball bounces off
+    pub fn normal(self) -> Vec2 {
+        match self {
+            Paddle::Bottom => Vec2::Y,
+            Paddle::Left => Vec2::X,
+            Paddle::Right => Vec2::NEG_X,
+        }
+    }
+
+    /// component of the vector on the axis the paddle moves along
+    pub fn along(self, vector: Vec2) -> f32 {
+        match self {
+            Paddle::Bottom => vector.x,
+            Paddle::Left | Paddle::Right => vector.y,
+        }
+    }
+}
+
+/// direction the paddle wants to move, from -1.0 to 1.0
+/// set by the player input, the cpu or a replay
+#[derive(Component, Default)]
+pub struct PaddleIntent(pub f32);
+
+impl PaddleIntent {
+    /// intent to reach the target on the movement axis in the next step,
+    /// slows down in front of the target so the paddle does not shake
+    pub fn towards(target: f32, position: f32, delta_secs: f32) -> Self {
+        let step = (PADDLE_SPEED * delta_secs).max(f32::EPSILON);
+        Self(((target - position) / step).clamp(-1.0, 1.0))
+    }
+}
+
 #[derive(Component)]
-struct Paddle;
-#[derive(Component)]
-struct Ball;
-#[derive(Component)]
-struct LeftWall;
-#[derive(Component)]
-struct RightWall;
-#[derive(Component)]
-struct BottomWall;
-#[derive(Component)]
-struct TopWall;
+pub struct Ball;
+
+/// the paddle which touched the ball last, None after a serve
+#[derive(Component, Default)]
+pub struct LastPaddle(pub Option<Paddle>);
+
+/// the walls around the game surface
+#[derive(Component, Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Wall {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Wall {
+    /// position and size of the wall on the game surface
+    fn transform(self, width: f32, height: f32, thickness: f32) -> Transform {
+        let (translation, scale) = match self {
+            Wall::Left => (
+                Vec3::new(-width, 0.0, 0.0),
+                Vec3::new(thickness, height * 2.0, 1.0),
+            ),
+            Wall::Right => (
+                Vec3::new(width, 0.0, 0.0),
+                Vec3::new(thickness, height * 2.0, 1.0),
+            ),
+            Wall::Top => (
+                Vec3::new(0.0, height, 0.0),
+                Vec3::new(width * 2.0, thickness * 2.0, 1.0),
+            ),
+            Wall::Bottom => (
+                Vec3::new(0.0, -height, 0.0),
+                Vec3::new(width * 2.0, thickness * 2.0, 1.0),
+            ),
+        };
+
+        Transform {
+            translation,
+            scale,
+            ..default()
+        }
+    }
+}
 
 /// Velocity related objects need this
 #[derive(Component, Deref, DerefMut)]
-struct Velocity(Vec2);
+pub struct Velocity(pub Vec2);
+
+/// position of moving objects in the simulation
+/// the Transform is interpolated between the previous and the current step
+#[derive(Component, Clone, Copy, Debug)]
+pub struct Position {
+    pub current: Vec2,
+    pub previous: Vec2,
+}
+
+impl Position {
+    pub fn new(position: Vec2) -> Self {
+        Self {
+            current: position,
+            previous: position,
+        }
+    }
+}
 
 /// Collition related objects need this
 #[derive(Component)]
-struct Collider;
+pub struct Collider;
 #[derive(Component)]
 /// Objects related to the end of the game
 struct ExitCondition;
 #[derive(Component)]
 /// Objects on the Screen need this
-struct OnGameScreen;
+pub struct OnGameScreen;
+
+/// random numbers of the simulation
+/// seeded for every game so the same inputs give the same game
 #[derive(Resource, Deref, DerefMut)]
-/// timer vor the game
-struct GameTimer(Timer);
+pub struct PongRng {
+    pub seed: u64,
+    #[deref]
+    rng: StdRng,
+}
+
+impl PongRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
 
 /// Position of the walls for the game surface
 #[allow(unused)]
 #[derive(Resource)]
-struct GameSurface {
-    left: f32,
-    right: f32,
-    top: f32,
-    bottom: f32,
+pub struct GameSurface {
+    pub left: f32,
+    pub right: f32,
+    pub top: f32,
+    pub bottom: f32,
 }
 
+/// logical size of the arena the walls are placed around
+#[derive(Resource, Clone, Copy, Deref, Debug)]
+pub struct ArenaSize(pub Vec2);
+
+impl Default for ArenaSize {
+    fn default() -> Self {
+        Self(ARENA_SIZE)
+    }
+}
+
+/// selected mode of the pong game
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq, Debug)]
+pub enum PongMode {
+    #[default]
+    Classic,
+    Breakout,
+    Versus,
+}
+
+/// send when the last ball touches a wall which is an exit condition
+#[derive(Event)]
+pub struct BallLost(pub Wall);
+
+/// send when a ball bounces off an object which is not a paddle
+#[derive(Event)]
+pub struct ObjectHit {
+    pub position: Vec2,
+    pub paddle: Option<Paddle>,
+}
+
+/// send when a ball bounces off a paddle
+#[derive(Event)]
+pub struct PaddleHit {
+    pub position: Vec2,
+    pub paddle: Entity,
+}
+
+/// the movement of paddles and ball in the fixed timestep,
+/// systems which react to the movement run after this set
+#[derive(SystemSet, Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct PongSimulation;
+
 /// Game state of pong
-#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
-enum PongState {
+#[derive(Serialize, Deserialize, Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+pub enum PongState {
     #[default]
     Disabled,
     Loading,
     Game,
+    Paused,
     GameOver,
 }
 
+/// the match is running or paused, the game screen stays while it is paused
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Hash)]
+pub struct InGame;
+
+impl ComputedStates for InGame {
+    type SourceStates = PongState;
+
+    fn compute(sources: PongState) -> Option<Self> {
+        matches!(sources, PongState::Game | PongState::Paused).then_some(InGame)
+    }
+}
+
 /// plugin to run the pong game after selection
 pub struct PongPlugin;
 impl Plugin for PongPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(GameState::Pong), game_setup)
-            .add_systems(OnExit(PongState::Game), despawn_screen::<OnGameScreen>)
-            .add_systems(OnEnter(PongState::Loading), pong_setup)
-            .add_systems(OnEnter(PongState::GameOver), game_exit)
-            .add_systems(Update, countdown.run_if(in_state(PongState::Loading)))
-            .add_systems(
-                Update,
-                (
-                    move_paddle,
-                    velocity_update,
-                    check_collision,
-                    exit_conditions,
-                )
-                    .chain()
-                    .run_if(in_state(PongState::Game)),
+        app.add_plugins((
+            PongLogicPlugin,
+            EndScreenPlugin,
+            ReplayPlugin,
+            PausePlugin,
+            CountdownPlugin,
+            InputPlugin,
+            HighScorePlugin,
+            EffectsPlugin,
+            SoundPlugin,
+            RulesPlugin,
+        ));
+    }
+}
+
+/// plugin with the rules and the simulation of the pong game
+/// it needs no window, so the game also runs headless
+pub struct PongLogicPlugin;
+impl Plugin for PongLogicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ScorePlugin,
+            BricksPlugin,
+            LevelPlugin,
+            VersusPlugin,
+            PowerUpPlugin,
+            NetworkPlugin,
+        ))
+        .add_event::<BallLost>()
+        .add_event::<ObjectHit>()
+        .add_event::<PaddleHit>()
+        .init_resource::<PongMode>()
+        .init_resource::<PaddleControllers>()
+        .init_resource::<ArenaSize>()
+        .init_resource::<MatchRules>()
+        .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+        .add_systems(OnEnter(GameState::Pong), game_setup)
+        .add_systems(OnExit(InGame), despawn_screen::<OnGameScreen>)
+        .add_systems(OnEnter(PongState::Loading), pong_setup)
+        .add_systems(
+            FixedUpdate,
+            (
+                store_previous_position,
+                player_intent.run_if(not(resource_exists::<ReplayPlayer>)),
+                replay_intent.run_if(resource_exists::<ReplayPlayer>),
+                record_intent.run_if(resource_exists::<ReplayRecorder>),
+                cpu_intent,
+                move_paddle,
+                velocity_update,
+                // the host of a network game moves the ball for the client
+                move_ball.run_if(not(is_network_client)),
             )
-            .init_state::<PongState>();
+                .chain()
+                .in_set(PongSimulation)
+                .run_if(simulation_running),
+        )
+        .add_systems(
+            RunFixedMainLoop,
+            interpolate_transforms
+                .in_set(RunFixedMainLoopSystem::AfterFixedMainLoop)
+                .run_if(in_state(PongState::Game)),
+        )
+        .init_state::<PongState>()
+        .add_computed_state::<InGame>();
     }
 }
 
 /// change the PongState::dissabled to PongState::Loading
 /// switch from GameState to PongState
-fn game_setup(mut commands: Commands, mut pong_state: ResMut<NextState<PongState>>) {
-    commands.insert_resource(GameTimer(Timer::from_seconds(2.0, TimerMode::Once)));
-    pong_state.set(PongState::Loading);
+pub fn game_setup(
+    mut commands: Commands,
+    mut pong_state: ResMut<NextState<PongState>>,
+    player: Option<ResMut<ReplayPlayer>>,
+    rules: Res<MatchRules>,
+) {
+    new_game(
+        &mut commands,
+        &mut pong_state,
+        player.map(|player| player.into_inner()),
+        &rules,
+    );
 }
 
-/// change the PongState to GameState to swithc to the Menu
-fn game_exit(mut game_state: ResMut<NextState<GameState>>) {
-    println!("Game over");
-    // FIXME nicht in Menu sondern endscreen(PongState::EndScreen)
-    game_state.set(GameState::Menu);
+/// resets score and level and starts loading a new game
+/// a replay starts again from the beginning, every other game gets recorded
+pub fn new_game(
+    commands: &mut Commands,
+    pong_state: &mut NextState<PongState>,
+    player: Option<&mut ReplayPlayer>,
+    rules: &MatchRules,
+) {
+    let seed = match player {
+        Some(player) => {
+            player.rewind();
+            player.seed()
+        }
+        None => {
+            let seed = rand::random();
+            commands.insert_resource(ReplayRecorder::new(seed));
+            seed
+        }
+    };
+    commands.insert_resource(PongRng::new(seed));
+    commands.insert_resource(Score::with_lives(rules.lives));
+    commands.insert_resource(VersusScore::default());
+    commands.insert_resource(BrickLevel::default());
+    pong_state.set(PongState::Loading);
 }
 
-/// spawns the window, the ball and the paddle
-// FIXME window shoud get generate in the main menu
-fn pong_setup(
+/// spawns the walls, the ball and the paddle
+/// the game surface has the size of the arena, independent of the window
+#[allow(clippy::too_many_arguments)]
+pub fn pong_setup(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    windows: Query<&Window>,
+    levels: Res<PongLevels>,
+    level_assets: Res<Assets<PongLevel>>,
+    mode: Res<PongMode>,
+    brick_level: Res<BrickLevel>,
+    controllers: Res<PaddleControllers>,
+    arena: Res<ArenaSize>,
+    rules: Res<MatchRules>,
+    mut player: Option<ResMut<ReplayPlayer>>,
+    mut recorder: Option<ResMut<ReplayRecorder>>,
 ) {
-    // get window positions
-    let window = match windows.single() {
-        Ok(window) => window,
-        Err(e) => {
-            eprintln!("No access to a single window: {}", e);
-            return;
-        }
-    };
-
-    // set window positions
-    let width = window.width() / 2.0;
-    let height = window.height() / 2.0;
+    let mut level = select_level(
+        &mut commands,
+        &levels,
+        &level_assets,
+        *mode,
+        &brick_level,
+        &rules,
+    );
+
+    // set arena positions
+    let width = arena.x / 2.0;
+    let height = arena.y / 2.0;
     let thickness = 1.0;
+
+    // a replay plays with the level of the recorded match
+    if let Some(segment) = player.as_mut().and_then(|player| player.start_segment()) {
+        level = segment.level.clone();
+        commands.insert_resource(CurrentLevel(level.clone()));
+    }
+    if let Some(recorder) = recorder.as_mut() {
+        recorder.start_segment(&level);
+    }
     commands.insert_resource(GameSurface {
         left: -width,
         right: width,
@@ -138,209 +436,373 @@ fn pong_setup(
         bottom: -height,
     });
 
-    //Walls
+    //Arena
     commands.spawn((
-        Sprite::from_color(WALL_COLOR, Vec2::ONE), // generate square
-        Transform {
-            translation: Vec3::new(-width, 0.0, 0.0), // position
-            scale: Vec3::new(thickness, height * 2.0, 1.0),
-            ..default()
-        },
-        LeftWall,
-        Collider,
-        OnGameScreen,
-    ));
-    commands.spawn((
-        Sprite::from_color(WALL_COLOR, Vec2::ONE),
-        Transform {
-            translation: Vec3::new(width, 0.0, 0.0),
-            scale: Vec3::new(thickness, height * 2.0, 1.0),
-            ..default()
-        },
-        RightWall,
-        Collider,
-        OnGameScreen,
-    ));
-    commands.spawn((
-        Sprite::from_color(WALL_COLOR, Vec2::ONE),
-        Transform {
-            translation: Vec3::new(0.0, height, 0.0),
-            scale: Vec3::new(width * 2.0, thickness * 2.0, 1.0),
-            ..default()
-        },
-        TopWall,
-        Collider,
-        OnGameScreen,
-    ));
-    commands.spawn((
-        Sprite::from_color(WALL_COLOR, Vec2::ONE),
-        Transform {
-            translation: Vec3::new(0.0, -height, 0.0),
-            scale: Vec3::new(width * 2.0, thickness * 2.0, 1.0),
-            ..default()
-        },
-        BottomWall,
-        ExitCondition,
+        Sprite::from_color(ARENA_COLOR, arena.0),
+        Transform::from_xyz(0.0, 0.0, -1.0),
         OnGameScreen,
     ));
 
+    //Walls
+    for wall in [Wall::Left, Wall::Right, Wall::Top, Wall::Bottom] {
+        let mut wall_entity = commands.spawn((
+            Sprite::from_color(WALL_COLOR, Vec2::ONE), // generate square
+            wall.transform(width, height, thickness),
+            wall,
+            OnGameScreen,
+        ));
+        // the level decides which walls end the round
+        if level.exit_walls.contains(&wall) {
+            wall_entity.insert(ExitCondition);
+        } else {
+            wall_entity.insert(Collider);
+        }
+    }
+
     //Paddle
-    commands.spawn((
-        Sprite::from_color(PADDLE_COLOR, Vec2::ONE),
-        Transform {
-            translation: Vec3::new(0.0, -height + 100.0, 0.0),
-            scale: PADDLE_SIZE.extend(1.0),
-            ..default()
-        },
-        Paddle,
-        Collider,
-        OnGameScreen,
-    ));
+    let paddles = match *mode {
+        PongMode::Classic | PongMode::Breakout => vec![Paddle::Bottom],
+        PongMode::Versus => vec![Paddle::Left, Paddle::Right],
+    };
+    for paddle in paddles {
+        let (translation, scale) = match paddle {
+            Paddle::Bottom => (
+                Vec3::new(0.0, -height + PADDLE_WALL_DISTANCE, 0.0),
+                Vec3::new(level.paddle_width, PADDLE_SIZE.y, 1.0),
+            ),
+            Paddle::Left => (
+                Vec3::new(-width + PADDLE_WALL_DISTANCE, 0.0, 0.0),
+                Vec3::new(PADDLE_SIZE.y, level.paddle_width, 1.0),
+            ),
+            Paddle::Right => (
+                Vec3::new(width - PADDLE_WALL_DISTANCE, 0.0, 0.0),
+                Vec3::new(PADDLE_SIZE.y, level.paddle_width, 1.0),
+            ),
+        };
+
+        let mut paddle_entity = commands.spawn((
+            Sprite::from_color(PADDLE_COLOR, Vec2::ONE),
+            Transform {
+                translation,
+                scale,
+                ..default()
+            },
+            Position::new(translation.truncate()),
+            paddle,
+            PaddleIntent::default(),
+            ActivePowerUps::default(),
+            Collider,
+            OnGameScreen,
+        ));
+        match controllers.get(paddle) {
+            Controller::Cpu(difficulty) => {
+                paddle_entity.insert(CpuPlayer::new(difficulty));
+            }
+            Controller::Remote => {
+                paddle_entity.insert(RemotePlayer);
+            }
+            Controller::Human => {}
+        }
+    }
 
     //Ball
-    commands.spawn((
-        Mesh2d(meshes.add(Circle::default())), // generate circle
-        MeshMaterial2d(materials.add(BALL_COLOR)),
-        Transform::from_translation(BALL_SPAWN_POINT)
+    commands.spawn(ball_bundle(
+        meshes.add(Circle::default()), // generate circle
+        materials.add(BALL_COLOR),
+        BALL_SPAWN_POINT.truncate(),
+        INITIAL_BALL_DIRECTION.normalize() * level.ball_speed, // sets the speed + direction
+    ));
+}
+
+/// components of a ball at the position with the velocity
+pub fn ball_bundle(
+    mesh: Handle<Mesh>,
+    material: Handle<ColorMaterial>,
+    position: Vec2,
+    velocity: Vec2,
+) -> impl Bundle {
+    (
+        Mesh2d(mesh),
+        MeshMaterial2d(material),
+        Transform::from_translation(position.extend(0.0))
             .with_scale(Vec2::splat(BALL_DIAMETER).extend(1.0)),
+        Position::new(position),
         Ball,
-        Velocity(INITIAL_BALL_DIRECTION.normalize() * BALL_SPEED), // sets the speed + direction
+        Velocity(velocity),
+        LastPaddle::default(),
         OnGameScreen,
-    ));
+    )
 }
 
-/// wait ... seconds bevor the game starts
-/// switch from PongState::GameLoading to PongState::Game
-fn countdown(
-    mut game_state: ResMut<NextState<PongState>>,
-    time: Res<Time>,
-    mut timer: ResMut<GameTimer>,
+/// the simulation runs in the game until the state is about to change,
+/// so the result does not depend on how many steps run in the last frame
+/// a replay stops after the last recorded step
+pub fn simulation_running(
+    state: Res<State<PongState>>,
+    next_state: Res<NextState<PongState>>,
+    player: Option<Res<ReplayPlayer>>,
+) -> bool {
+    *state.get() == PongState::Game
+        && matches!(*next_state, NextState::Unchanged)
+        && player.is_none_or(|player| !player.segment_finished())
+}
+
+/// saves the position of the last step for the interpolation
+fn store_previous_position(mut query: Query<&mut Position>) {
+    for mut position in &mut query {
+        position.previous = position.current;
+    }
+}
+
+/// moves the Transform between the last two simulation steps
+/// so the movement looks smooth with every framerate
+fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &Position)>,
 ) {
-    if timer.tick(time.delta()).finished() {
-        game_state.set(PongState::Game);
+    let alpha = fixed_time.overstep_fraction();
+    for (mut transform, position) in &mut query {
+        let z = transform.translation.z;
+        transform.translation = position.previous.lerp(position.current, alpha).extend(z);
     }
 }
 
-/// logic for moving the paddle
+/// logic for moving the paddles
 fn move_paddle(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    mut paddle: Single<&mut Transform, With<Paddle>>,
+    mut paddles: Query<(&mut Position, &Transform, &Paddle, &PaddleIntent)>,
     screen: Res<GameSurface>,
     time: Res<Time>,
 ) {
-    let mut direction = 0.0;
-
-    if keyboard_input.pressed(KeyCode::ArrowLeft) {
-        direction -= 1.0;
-    }
-    if keyboard_input.pressed(KeyCode::ArrowRight) {
-        direction += 1.0;
+    for (mut position, transform, paddle, intent) in &mut paddles {
+        position.current = paddle_step(
+            *paddle,
+            position.current,
+            transform.scale,
+            intent.0,
+            &screen,
+            time.delta_secs(),
+        );
     }
+}
 
-    let left_wall = screen.left + PADDLE_SIZE.x / 2.0;
-    let right_wall = screen.right - PADDLE_SIZE.x / 2.0;
-
-    let new_paddle_position = paddle.translation.x + direction * PADDLE_SPEED * time.delta_secs();
-    paddle.translation.x = new_paddle_position.clamp(left_wall, right_wall);
+/// position of the paddle after one step with the intent
+/// the bottom paddle moves horizontal, the side paddles vertical, both stop at the walls
+pub fn paddle_step(
+    paddle: Paddle,
+    position: Vec2,
+    size: Vec3,
+    intent: f32,
+    screen: &GameSurface,
+    delta_secs: f32,
+) -> Vec2 {
+    let movement = intent.clamp(-1.0, 1.0) * PADDLE_SPEED * delta_secs;
+
+    match paddle {
+        Paddle::Bottom => {
+            let left_wall = screen.left + size.x / 2.0;
+            let right_wall = screen.right - size.x / 2.0;
+            position.with_x((position.x + movement).clamp(left_wall, right_wall))
+        }
+        Paddle::Left | Paddle::Right => {
+            let bottom_wall = screen.bottom + size.y / 2.0;
+            let top_wall = screen.top - size.y / 2.0;
+            position.with_y((position.y + movement).clamp(bottom_wall, top_wall))
+        }
+    }
 }
 
-/// all objects with struct velocity get moved her
-fn velocity_update(mut query: Query<(&mut Transform, &Velocity)>, time: Res<Time>) {
-    for (mut transform, velocity) in &mut query {
-        transform.translation.x += velocity.x * time.delta_secs();
-        transform.translation.y += velocity.y * time.delta_secs();
+/// all objects with struct velocity except the ball get moved her
+/// the ball is moved in move_ball
+fn velocity_update(mut query: Query<(&mut Position, &Velocity), Without<Ball>>, time: Res<Time>) {
+    for (mut position, velocity) in &mut query {
+        position.current += velocity.0 * time.delta_secs();
     }
 }
 
-/// saves position where the collission is detected
+/// object the ball hits during its movement
 #[derive(Debug, PartialEq, Clone, Copy)]
-enum Collision {
-    Left,
-    Right,
-    Top,
-    Bottom,
+enum HitObject {
+    Collider(Entity),
+    Exit(Wall),
 }
 
-/// check collision with ball and objects that have the collider struct
-/// change their direction to the opposite
-fn check_collision(
-    ball: Single<(&Transform, &mut Velocity), With<Ball>>,
-    collider_query: Query<&Transform, With<Collider>>,
+/// moves the balls and bounces them off every object with the collider struct on their way
+/// the first contact is searched with a sweep, so a ball can bounce several times in one step
+/// every paddle hit gives points and is send as PaddleHit,
+/// brick hits are send as BrickHit and other bounces as ObjectHit
+/// on the face of a paddle the ball bounces with an angle and gets faster,
+/// a sticky paddle holds the ball instead
+/// a ball on a wall that is an exit condition is removed, only the last ball sends BallLost
+/// and gets served again
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn move_ball(
+    mut commands: Commands,
+    mut balls: Query<
+        (Entity, &mut Position, &mut Velocity, &mut LastPaddle),
+        (With<Ball>, Without<Stuck>),
+    >,
+    all_balls: Query<(), With<Ball>>,
+    collider_query: Query<
+        (
+            Entity,
+            &Transform,
+            Option<&Position>,
+            Option<(&Paddle, &PaddleIntent, &ActivePowerUps)>,
+            Has<Brick>,
+        ),
+        (With<Collider>, Without<Ball>),
+    >,
+    exit_query: Query<(&Transform, &Wall), (With<ExitCondition>, Without<Ball>)>,
+    level: Res<CurrentLevel>,
+    mut score: ResMut<Score>,
+    mut brick_hits: EventWriter<BrickHit>,
+    mut object_hits: EventWriter<ObjectHit>,
+    mut paddle_hits: EventWriter<PaddleHit>,
+    mut ball_lost: EventWriter<BallLost>,
+    time: Res<Time>,
 ) {
-    let (ball_transform, mut ball_velocity) = ball.into_inner();
-
-    for collider_transform in collider_query {
-        let collision = ball_collision(
-            BoundingCircle::new(ball_transform.translation.truncate(), BALL_DIAMETER / 2.),
-            Aabb2d::new(
-                collider_transform.translation.truncate(),
-                collider_transform.scale.truncate() / 2.,
-            ),
-        );
-
-        if let Some(collision) = collision {
-            let mut reflect_x = false;
-            let mut reflect_y = false;
-
-            match collision {
-                Collision::Left => reflect_x = ball_velocity.x > 0.0,
-                Collision::Right => reflect_x = ball_velocity.x < 0.0,
-                Collision::Top => reflect_y = ball_velocity.y < 0.0,
-                Collision::Bottom => reflect_y = ball_velocity.y > 0.0,
+    let radius = BALL_DIAMETER / 2.0;
+    let time_scale = ball_time_scale(
+        collider_query
+            .iter()
+            .filter_map(|(_, _, _, paddle, _)| paddle.map(|(_, _, power_ups)| power_ups)),
+    );
+    let mut balls_left = all_balls.iter().count();
+
+    for (ball, mut ball_position, mut ball_velocity, mut last_paddle) in &mut balls {
+        let mut remaining = time.delta_secs() * time_scale;
+
+        for _ in 0..MAX_BOUNCES_PER_STEP {
+            let position = ball_position.current;
+            let motion = ball_velocity.0 * remaining;
+
+            // search the first contact, exits win if they are hit at the same time
+            let mut first_hit: Option<(Hit, HitObject)> = None;
+            for (entity, collider_transform, collider_position, _, _) in &collider_query {
+                let aabb = bounding_box(collider_transform, collider_position);
+                if let Some(hit) = sweep_circle_aabb(position, radius, motion, aabb)
+                    && first_hit.is_none_or(|(first, _)| hit.time < first.time)
+                {
+                    first_hit = Some((hit, HitObject::Collider(entity)));
+                }
             }
-
-            if reflect_x {
-                ball_velocity.x = -ball_velocity.x;
+            for (exit_transform, wall) in &exit_query {
+                if let Some(hit) =
+                    sweep_circle_aabb(position, radius, motion, bounding_box(exit_transform, None))
+                    && first_hit.is_none_or(|(first, _)| hit.time <= first.time)
+                {
+                    first_hit = Some((hit, HitObject::Exit(*wall)));
+                }
             }
-            if reflect_y {
-                ball_velocity.y = -ball_velocity.y;
+
+            let Some((hit, object)) = first_hit else {
+                ball_position.current += motion;
+                break;
+            };
+
+            ball_position.current += motion * hit.time;
+            remaining *= 1.0 - hit.time;
+
+            match object {
+                HitObject::Exit(wall) => {
+                    // the rally goes on while other balls are in the game
+                    if balls_left > 1 {
+                        balls_left -= 1;
+                        commands.entity(ball).despawn();
+                    } else {
+                        ball_lost.write(BallLost(wall));
+                        // no interpolation from the wall back to the spawn point
+                        *ball_position = Position::new(BALL_SPAWN_POINT.truncate());
+                        ball_velocity.0 = serve_direction(wall) * level.ball_speed;
+                        last_paddle.0 = None;
+                    }
+                    break;
+                }
+                HitObject::Collider(entity) => {
+                    let Ok((_, collider_transform, collider_position, paddle, is_brick)) =
+                        collider_query.get(entity)
+                    else {
+                        continue;
+                    };
+
+                    ball_velocity.0 = reflect(ball_velocity.0, hit.normal);
+
+                    let Some((paddle, intent, power_ups)) = paddle else {
+                        object_hits.write(ObjectHit {
+                            position: ball_position.current,
+                            paddle: last_paddle.0,
+                        });
+                        if is_brick {
+                            brick_hits.write(BrickHit(entity));
+                        }
+                        continue;
+                    };
+
+                    last_paddle.0 = Some(*paddle);
+                    paddle_hits.write(PaddleHit {
+                        position: ball_position.current,
+                        paddle: entity,
+                    });
+                    score.points += POINTS_PER_HIT;
+                    if hit.normal.dot(paddle.normal()) > PADDLE_FACE_THRESHOLD {
+                        let paddle_box = bounding_box(collider_transform, collider_position);
+                        let speed = (ball_velocity.length() * BALL_SPEED_UP)
+                            .min(level.ball_speed * MAX_BALL_SPEED_FACTOR);
+                        ball_velocity.0 =
+                            paddle_bounce(*paddle, ball_position.current, paddle_box, intent.0)
+                                * speed;
+
+                        if power_ups.has(PowerUp::StickyPaddle) {
+                            commands.entity(ball).insert(Stuck::new(
+                                entity,
+                                ball_position.current - paddle_box.center(),
+                            ));
+                            break;
+                        }
+                    }
+                }
             }
         }
     }
 }
 
-/// check if ball colides with the bottom wall
-/// if true change PongState to PongState::GameOver
-fn exit_conditions(
-    exit_query: Query<&Transform, With<ExitCondition>>,
-    ball: Single<&Transform, With<Ball>>,
-    mut game_state: ResMut<NextState<PongState>>,
-) {
-    for exit_transform in exit_query {
-        let collision = ball_collision(
-            BoundingCircle::new(ball.translation.truncate(), BALL_DIAMETER / 2.),
-            Aabb2d::new(
-                exit_transform.translation.truncate(),
-                exit_transform.scale.truncate() / 2.,
-            ),
-        );
-        if let Some(collision) = collision {
-            if collision == Collision::Top {
-                game_state.set(PongState::GameOver);
-            }
-        }
-    }
+/// bounding box of a sprite which is scaled to its size
+/// moving objects are at their simulation position
+pub fn bounding_box(transform: &Transform, position: Option<&Position>) -> Aabb2d {
+    let center = position.map_or(transform.translation.truncate(), |position| {
+        position.current
+    });
+    Aabb2d::new(center, transform.scale.truncate() / 2.)
 }
 
-/// check ball collision with one specific object which is got from check_collison()
-fn ball_collision(ball: BoundingCircle, bounding_box: Aabb2d) -> Option<Collision> {
-    if !ball.intersects(&bounding_box) {
-        return None;
+/// direction of the ball after it bounced off the face of a paddle
+/// hits on the edge of the paddle give a steeper angle than hits in the middle,
+/// a moving paddle adds some english in its direction
+pub fn paddle_bounce(paddle: Paddle, ball_position: Vec2, paddle_box: Aabb2d, intent: f32) -> Vec2 {
+    let offset = ball_position - paddle_box.center();
+    let half_size = paddle_box.half_size();
+    let (offset, half_length) = match paddle {
+        Paddle::Bottom => (offset.x, half_size.x),
+        Paddle::Left | Paddle::Right => (offset.y, half_size.y),
+    };
+
+    let hit_position = (offset / half_length).clamp(-1.0, 1.0);
+    let angle = (hit_position * MAX_BOUNCE_ANGLE + intent.clamp(-1.0, 1.0) * PADDLE_ENGLISH_ANGLE)
+        .clamp(-MAX_BOUNCE_ANGLE, MAX_BOUNCE_ANGLE);
+
+    match paddle {
+        Paddle::Bottom => Vec2::new(angle.sin(), angle.cos()),
+        Paddle::Left => Vec2::new(angle.cos(), angle.sin()),
+        Paddle::Right => Vec2::new(-angle.cos(), angle.sin()),
     }
+}
 
-    let closest = bounding_box.closest_point(ball.center);
-    let offset = ball.center - closest;
-    let side = if offset.x.abs() > offset.y.abs() {
-        if offset.x < 0. {
-            Collision::Left
-        } else {
-            Collision::Right
-        }
-    } else if offset.y > 0. {
-        Collision::Top
-    } else {
-        Collision::Bottom
+/// direction of a served ball, it flies to the side where the last ball was lost
+fn serve_direction(wall: Wall) -> Vec2 {
+    let direction = match wall {
+        Wall::Left => Vec2::new(-INITIAL_BALL_DIRECTION.x.abs(), INITIAL_BALL_DIRECTION.y),
+        Wall::Right => Vec2::new(INITIAL_BALL_DIRECTION.x.abs(), INITIAL_BALL_DIRECTION.y),
+        Wall::Top | Wall::Bottom => INITIAL_BALL_DIRECTION,
     };
-
-    Some(side)
+    direction.normalize()
 }
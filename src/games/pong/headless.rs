@@ -0,0 +1,338 @@
+/// runs the pong simulation without a window, for the tests
+use std::time::Duration;
+
+use bevy::{
+    asset::AssetPlugin, input::InputPlugin, prelude::*, state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+};
+
+use crate::enums::GameState;
+use crate::games::pong::{
+    ai::PaddleControllers,
+    game::{
+        ArenaSize, Ball, BallLost, ObjectHit, Paddle, PaddleHit, PongLogicPlugin, PongMode,
+        PongRng, PongState, Position, SIMULATION_HZ, Velocity, simulation_running,
+    },
+    input::InputBindings,
+    level::{CurrentLevel, PongLevel, PongLevels},
+    power_ups::PowerUp,
+    replay::{Replay, ReplayPlayer, ReplayRecorder},
+    rules::MatchRules,
+    score::Score,
+    versus::VersusScore,
+};
+
+// headless constanten
+/// seed of the headless games, so the tests always play the same game
+pub const SEED: u64 = 42;
+/// frames one simulation step may take before the harness gives up
+const MAX_FRAMES_PER_STEP: u32 = 4;
+
+/// simulation steps since the game was started
+#[derive(Resource, Default)]
+struct SimulationSteps(u32);
+
+/// events of the simulation, counted since the game was started
+#[derive(Resource, Default, Debug)]
+pub struct SimulationEvents {
+    pub object_hits: u32,
+    pub paddle_hits: u32,
+    pub balls_lost: u32,
+}
+
+/// a pong game without window, every update runs one simulation step
+pub struct HeadlessPong {
+    pub app: App,
+    /// seed of a recorded game, a replay keeps the seed of its recording
+    seed: u64,
+}
+
+impl HeadlessPong {
+    /// builds the app for the mode, the level and the size of the arena
+    /// the paddles are moved with the default key bindings
+    pub fn new(mode: PongMode, level: PongLevel, arena: Vec2) -> Self {
+        let mut app = App::new();
+        app.add_plugins((
+            MinimalPlugins,
+            StatesPlugin,
+            InputPlugin,
+            AssetPlugin {
+                watch_for_changes_override: Some(false),
+                ..default()
+            },
+        ))
+        .init_asset::<Mesh>()
+        .init_asset::<ColorMaterial>()
+        .init_state::<GameState>()
+        .init_resource::<InputBindings>()
+        .init_resource::<SimulationSteps>()
+        .init_resource::<SimulationEvents>()
+        .insert_resource(mode)
+        .insert_resource(PaddleControllers::default())
+        .insert_resource(ArenaSize(arena))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(
+            1.0 / SIMULATION_HZ,
+        )))
+        .add_plugins(PongLogicPlugin)
+        // the step is counted before the simulation can end the game
+        .add_systems(FixedPreUpdate, count_step.run_if(simulation_running))
+        .add_systems(FixedPostUpdate, count_events);
+
+        let level = app
+            .world_mut()
+            .resource_mut::<Assets<PongLevel>>()
+            .add(level);
+        app.insert_resource(PongLevels::single(level));
+        app.finish();
+        app.cleanup();
+
+        Self { app, seed: SEED }
+    }
+
+    /// adds the resource before the game starts, like the controllers or a network connection
+    pub fn with_resource(mut self, resource: impl Resource) -> Self {
+        self.app.insert_resource(resource);
+        self
+    }
+
+    /// time of one frame instead of one simulation step,
+    /// a frame then runs no or several steps like with a real framerate
+    pub fn with_frame_time(self, frame: Duration) -> Self {
+        self.with_resource(TimeUpdateStrategy::ManualDuration(frame))
+    }
+
+    /// plays the game with another seed
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// starts the game and skips the countdown
+    pub fn start(mut self) -> Self {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Pong);
+        self.update_until(|world| {
+            *world.resource::<State<PongState>>().get() == PongState::Loading
+        });
+
+        if !self.app.world().contains_resource::<ReplayPlayer>() {
+            // the recording starts again with the seed of the harness instead of a random one
+            let mut recorder = ReplayRecorder::new(self.seed);
+            recorder.start_segment(self.app.world().resource::<CurrentLevel>());
+            self.app.insert_resource(recorder);
+            self.app.insert_resource(PongRng::new(self.seed));
+        }
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<PongState>>()
+            .set(PongState::Game);
+        self.update_until(|world| *world.resource::<State<PongState>>().get() == PongState::Game);
+        self
+    }
+
+    /// runs updates until the condition is true
+    fn update_until(&mut self, condition: impl Fn(&World) -> bool) {
+        for _ in 0..MAX_FRAMES_PER_STEP {
+            self.app.update();
+            if condition(self.app.world()) {
+                return;
+            }
+        }
+        panic!("the headless game did not reach the expected state");
+    }
+
+    /// runs the simulation steps, a game that ends stops the simulation early
+    /// with frames of several steps the last frame can run more steps
+    pub fn step(&mut self, steps: u32) {
+        for _ in 0..steps {
+            if self.state() != PongState::Game || self.state_pending() {
+                break;
+            }
+            let target = self.steps() + 1;
+            self.update_until(|world| world.resource::<SimulationSteps>().0 >= target);
+        }
+        // the state changes in the update after the step which ended the game
+        if self.state_pending() {
+            self.app.update();
+        }
+    }
+
+    /// runs single steps until the condition is true, at most the given number of steps
+    /// returns false if the condition was never true
+    pub fn step_until(&mut self, max_steps: u32, condition: impl Fn(&Self) -> bool) -> bool {
+        for _ in 0..max_steps {
+            self.step(1);
+            if condition(self) {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// changes the state in the next update, like the pause menu does
+    pub fn set_state(&mut self, state: PongState) {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<PongState>>()
+            .set(state);
+    }
+
+    /// leaves the game to the menu, like the quit button of the pause menu
+    pub fn quit(&mut self) {
+        self.app
+            .world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Menu);
+        self.app.update();
+    }
+
+    /// runs one update, the simulation only steps in PongState::Game
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// true if the simulation has changed the state for the next update
+    fn state_pending(&self) -> bool {
+        matches!(
+            self.app.world().resource::<NextState<PongState>>(),
+            NextState::Pending(_)
+        )
+    }
+
+    /// simulation steps since the game was started
+    pub fn steps(&self) -> u32 {
+        self.app.world().resource::<SimulationSteps>().0
+    }
+
+    pub fn state(&self) -> PongState {
+        *self.app.world().resource::<State<PongState>>().get()
+    }
+
+    pub fn score(&self) -> &Score {
+        self.app.world().resource::<Score>()
+    }
+
+    pub fn rules(&self) -> &MatchRules {
+        self.app.world().resource::<MatchRules>()
+    }
+
+    pub fn versus_score(&self) -> &VersusScore {
+        self.app.world().resource::<VersusScore>()
+    }
+
+    pub fn events(&self) -> &SimulationEvents {
+        self.app.world().resource::<SimulationEvents>()
+    }
+
+    /// the replay of the steps played so far
+    pub fn replay(&self) -> Replay {
+        let world = self.app.world();
+        world.resource::<ReplayRecorder>().replay(
+            *world.resource::<PongMode>(),
+            world.resource::<PaddleControllers>(),
+            world.resource::<MatchRules>(),
+        )
+    }
+
+    /// presses the key until it is released
+    pub fn press(&mut self, key: KeyCode) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+    }
+
+    pub fn release(&mut self, key: KeyCode) {
+        self.app
+            .world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+    }
+
+    /// position and velocity of the ball
+    pub fn ball(&mut self) -> (Vec2, Vec2) {
+        let world = self.app.world_mut();
+        let (position, velocity) = world
+            .query_filtered::<(&Position, &Velocity), With<Ball>>()
+            .single(world)
+            .expect("the game has one ball");
+        (position.current, velocity.0)
+    }
+
+    /// moves the ball to the position with the velocity
+    pub fn set_ball(&mut self, position: Vec2, velocity: Vec2) {
+        let world = self.app.world_mut();
+        let (mut ball_position, mut ball_velocity) = world
+            .query_filtered::<(&mut Position, &mut Velocity), With<Ball>>()
+            .single_mut(world)
+            .expect("the game has one ball");
+        *ball_position = Position::new(position);
+        ball_velocity.0 = velocity;
+    }
+
+    /// number of balls in the game
+    pub fn ball_count(&mut self) -> usize {
+        let world = self.app.world_mut();
+        world.query_filtered::<(), With<Ball>>().iter(world).count()
+    }
+
+    /// moves every ball, the balls get the positions and velocities in query order
+    pub fn set_balls(&mut self, balls: &[(Vec2, Vec2)]) {
+        let world = self.app.world_mut();
+        let mut query = world.query_filtered::<(&mut Position, &mut Velocity), With<Ball>>();
+        for ((mut ball_position, mut ball_velocity), (position, velocity)) in
+            query.iter_mut(world).zip(balls)
+        {
+            *ball_position = Position::new(*position);
+            ball_velocity.0 = *velocity;
+        }
+    }
+
+    /// puts the power-up on the paddle, it is caught in the next step
+    pub fn catch_power_up(&mut self, paddle: Paddle, power_up: PowerUp) {
+        let position = self.paddle(paddle);
+        self.app
+            .world_mut()
+            .spawn((Position::new(position), power_up));
+    }
+
+    /// length of the paddle along its movement axis
+    pub fn paddle_width(&mut self, paddle: Paddle) -> f32 {
+        let world = self.app.world_mut();
+        world
+            .query::<(&Transform, &Paddle)>()
+            .iter(world)
+            .find(|(_, other)| **other == paddle)
+            .map(|(transform, _)| paddle.along(transform.scale.truncate()))
+            .expect("the game has the paddle")
+    }
+
+    /// position of the paddle
+    pub fn paddle(&mut self, paddle: Paddle) -> Vec2 {
+        let world = self.app.world_mut();
+        world
+            .query::<(&Position, &Paddle)>()
+            .iter(world)
+            .find(|(_, other)| **other == paddle)
+            .map(|(position, _)| position.current)
+            .expect("the game has the paddle")
+    }
+}
+
+fn count_step(mut steps: ResMut<SimulationSteps>) {
+    steps.0 += 1;
+}
+
+/// counts the events the simulation has sent
+fn count_events(
+    mut events: ResMut<SimulationEvents>,
+    mut object_hits: EventReader<ObjectHit>,
+    mut paddle_hits: EventReader<PaddleHit>,
+    mut ball_lost: EventReader<BallLost>,
+) {
+    events.object_hits += object_hits.read().count() as u32;
+    events.paddle_hits += paddle_hits.read().count() as u32;
+    events.balls_lost += ball_lost.read().count() as u32;
+}
@@ -0,0 +1,291 @@
+/// the local high score tables of the pong game
+use std::collections::HashMap;
+
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::functions::{ConfigError, load_data, save_data};
+use crate::games::pong::{
+    ai::{Controller, Difficulty, PaddleControllers},
+    end_screen::end_screen_setup,
+    game::{PongMode, PongState},
+    replay::ReplayPlayer,
+    rules::MatchRules,
+    score::Score,
+    versus::VersusScore,
+};
+
+// high score constanten
+const HIGH_SCORES_FILE: &str = "highscores.ron";
+/// entries of every table
+pub const TABLE_SIZE: usize = 10;
+const MAX_NAME_LENGTH: usize = 12;
+/// name of entries which were left without a name
+const DEFAULT_NAME: &str = "Player";
+/// shown when a game does not count because of changed rules
+pub const DEFAULT_RULES_HINT: &str = "High scores need the default rules";
+
+/// every mode and cpu difficulty has its own table
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum HighScoreCategory {
+    Classic,
+    Breakout,
+    VersusCpu(Difficulty),
+}
+
+impl HighScoreCategory {
+    pub const ALL: [HighScoreCategory; 5] = [
+        HighScoreCategory::Classic,
+        HighScoreCategory::Breakout,
+        HighScoreCategory::VersusCpu(Difficulty::Easy),
+        HighScoreCategory::VersusCpu(Difficulty::Medium),
+        HighScoreCategory::VersusCpu(Difficulty::Hard),
+    ];
+
+    /// the table of the game, games of two human players have none
+    pub fn of_game(mode: PongMode, controllers: &PaddleControllers) -> Option<Self> {
+        match (mode, controllers.left, controllers.right) {
+            (PongMode::Classic, _, _) => Some(HighScoreCategory::Classic),
+            (PongMode::Breakout, _, _) => Some(HighScoreCategory::Breakout),
+            (PongMode::Versus, Controller::Human, Controller::Cpu(difficulty))
+            | (PongMode::Versus, Controller::Cpu(difficulty), Controller::Human) => {
+                Some(HighScoreCategory::VersusCpu(difficulty))
+            }
+            (PongMode::Versus, _, _) => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HighScoreCategory::Classic => "Pong",
+            HighScoreCategory::Breakout => "Breakout",
+            HighScoreCategory::VersusCpu(Difficulty::Easy) => "CPU easy",
+            HighScoreCategory::VersusCpu(Difficulty::Medium) => "CPU medium",
+            HighScoreCategory::VersusCpu(Difficulty::Hard) => "CPU hard",
+        }
+    }
+}
+
+/// one entry of a table
+/// against the cpu the points are the goals the player won with
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct HighScore {
+    pub name: String,
+    pub points: u32,
+    pub time: f32,
+}
+
+/// all tables, saved in the data directory
+#[derive(Resource, Serialize, Deserialize, Default, Debug)]
+pub struct HighScores(HashMap<HighScoreCategory, Vec<HighScore>>);
+
+impl HighScores {
+    /// entries of the table, the best first
+    pub fn table(&self, category: HighScoreCategory) -> &[HighScore] {
+        self.0.get(&category).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// true if the result gets into the table
+    pub fn qualifies(&self, category: HighScoreCategory, points: u32, time: f32) -> bool {
+        let table = self.table(category);
+        points > 0
+            && (table.len() < TABLE_SIZE
+                || table
+                    .last()
+                    .is_some_and(|last| is_better(points, time, last)))
+    }
+
+    /// adds the entry and removes the entries after the table size
+    pub fn insert(&mut self, category: HighScoreCategory, entry: HighScore) {
+        let table = self.0.entry(category).or_default();
+        let rank = table
+            .iter()
+            .position(|other| is_better(entry.points, entry.time, other))
+            .unwrap_or(table.len());
+        table.insert(rank, entry);
+        table.truncate(TABLE_SIZE);
+    }
+}
+
+/// more points are better, with the same points the faster game
+fn is_better(points: u32, time: f32, other: &HighScore) -> bool {
+    points > other.points || (points == other.points && time < other.time)
+}
+
+/// a result that gets into a table and waits for the name of the player
+#[derive(Resource)]
+pub struct PendingHighScore {
+    category: HighScoreCategory,
+    pub name: String,
+    points: u32,
+    time: f32,
+}
+
+/// the game had a high score table, but it was played with changed rules
+#[derive(Resource)]
+pub struct ChangedRules;
+
+/// the text with the name while it is typed
+#[derive(Component)]
+pub struct NameEntryText;
+
+/// plugin for the high scores
+pub struct HighScorePlugin;
+impl Plugin for HighScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HighScores>()
+            .add_systems(Startup, load_high_scores)
+            .add_systems(
+                OnEnter(PongState::GameOver),
+                check_high_score
+                    .run_if(not(resource_exists::<ReplayPlayer>))
+                    .before(end_screen_setup),
+            )
+            .add_systems(
+                OnExit(PongState::GameOver),
+                (
+                    save_pending_high_score.run_if(resource_exists::<PendingHighScore>),
+                    remove_changed_rules,
+                ),
+            )
+            .add_systems(
+                Update,
+                name_entry
+                    .run_if(in_state(PongState::GameOver))
+                    .run_if(resource_exists::<PendingHighScore>),
+            );
+    }
+}
+
+/// reads the saved high scores, without a file all tables are empty
+fn load_high_scores(mut commands: Commands) {
+    match load_data::<HighScores>(HIGH_SCORES_FILE) {
+        Ok(Some(high_scores)) => commands.insert_resource(high_scores),
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// writes the high scores into the data directory
+fn save_high_scores(high_scores: &HighScores) -> Result<(), ConfigError> {
+    save_data(HIGH_SCORES_FILE, high_scores)
+}
+
+/// compares the result with the table of the game
+/// against the cpu only won games count, games with changed rules never
+/// and the end screen tells the player why
+pub fn check_high_score(
+    mut commands: Commands,
+    high_scores: Res<HighScores>,
+    mode: Res<PongMode>,
+    controllers: Res<PaddleControllers>,
+    rules: Res<MatchRules>,
+    score: Res<Score>,
+    versus_score: Res<VersusScore>,
+) {
+    let Some(category) = HighScoreCategory::of_game(*mode, &controllers) else {
+        return;
+    };
+    if *rules != MatchRules::default() {
+        commands.insert_resource(ChangedRules);
+        return;
+    }
+    let points = match category {
+        HighScoreCategory::Classic | HighScoreCategory::Breakout => score.points,
+        HighScoreCategory::VersusCpu(_) if controllers.left == Controller::Human => {
+            versus_score.left.saturating_sub(versus_score.right)
+        }
+        HighScoreCategory::VersusCpu(_) => versus_score.right.saturating_sub(versus_score.left),
+    };
+
+    if high_scores.qualifies(category, points, score.time) {
+        commands.insert_resource(PendingHighScore {
+            category,
+            name: String::new(),
+            points,
+            time: score.time,
+        });
+    }
+}
+
+fn remove_changed_rules(mut commands: Commands) {
+    commands.remove_resource::<ChangedRules>();
+}
+
+/// typing changes the name, enter saves the entry
+fn name_entry(
+    mut commands: Commands,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut pending: ResMut<PendingHighScore>,
+    mut high_scores: ResMut<HighScores>,
+    mut text: Single<&mut Text, With<NameEntryText>>,
+) {
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                add_high_score(&mut high_scores, &pending);
+                commands.remove_resource::<PendingHighScore>();
+                text.0 = format!("{} saved", name_or_default(&pending.name));
+                return;
+            }
+            Key::Backspace => {
+                pending.name.pop();
+            }
+            _ => {
+                if let Some(input) = &event.text {
+                    for character in input.chars().filter(|character| !character.is_control()) {
+                        if pending.name.chars().count() < MAX_NAME_LENGTH {
+                            pending.name.push(character);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    text.0 = name_entry_text(&pending.name);
+}
+
+/// text of the name while it is typed
+pub fn name_entry_text(name: &str) -> String {
+    format!("Name: {}_", name)
+}
+
+fn name_or_default(name: &str) -> &str {
+    let name = name.trim();
+    if name.is_empty() { DEFAULT_NAME } else { name }
+}
+
+/// adds the pending entry to its table and saves all tables
+fn add_high_score(high_scores: &mut HighScores, pending: &PendingHighScore) {
+    high_scores.insert(
+        pending.category,
+        HighScore {
+            name: name_or_default(&pending.name).to_string(),
+            points: pending.points,
+            time: pending.time,
+        },
+    );
+    if let Err(e) = save_high_scores(high_scores) {
+        eprintln!("{}", e);
+    }
+}
+
+/// an entry without confirmed name is saved when the end screen is left
+fn save_pending_high_score(
+    mut commands: Commands,
+    pending: Res<PendingHighScore>,
+    mut high_scores: ResMut<HighScores>,
+) {
+    add_high_score(&mut high_scores, &pending);
+    commands.remove_resource::<PendingHighScore>();
+}
@@ -0,0 +1,211 @@
+/// the input devices which move the paddles and their bindings
+use bevy::{prelude::*, window::PrimaryWindow};
+use serde::{Deserialize, Serialize};
+
+use crate::functions::{ConfigError, load_config, save_config};
+use crate::games::pong::{
+    ai::CpuPlayer,
+    game::{Paddle, PaddleIntent, Position},
+    network::RemotePlayer,
+};
+
+// input constanten
+const BINDINGS_FILE: &str = "input.ron";
+/// stick values below this are ignored
+const STICK_DEADZONE: f32 = 0.15;
+/// number of gamepads a binding can select
+pub const MAX_GAMEPADS: usize = 4;
+
+/// the device a player moves the paddle with
+#[derive(Serialize, Deserialize, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum InputDevice {
+    Keyboard,
+    Gamepad,
+    Mouse,
+    Touch,
+}
+
+impl InputDevice {
+    /// the device after this one in the settings
+    pub fn next(self) -> Self {
+        match self {
+            InputDevice::Keyboard => InputDevice::Gamepad,
+            InputDevice::Gamepad => InputDevice::Mouse,
+            InputDevice::Mouse => InputDevice::Touch,
+            InputDevice::Touch => InputDevice::Keyboard,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            InputDevice::Keyboard => "Keyboard",
+            InputDevice::Gamepad => "Gamepad",
+            InputDevice::Mouse => "Mouse",
+            InputDevice::Touch => "Touch",
+        }
+    }
+}
+
+/// controls of the player of one paddle
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PaddleBinding {
+    pub device: InputDevice,
+    /// keys to move the paddle to the negative and positive direction
+    pub negative: KeyCode,
+    pub positive: KeyCode,
+    /// number of the gamepad, the gamepads are sorted by their entity
+    pub gamepad: usize,
+}
+
+/// the bindings of every paddle, saved in the config directory
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+pub struct InputBindings {
+    pub bottom: PaddleBinding,
+    pub left: PaddleBinding,
+    pub right: PaddleBinding,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            bottom: PaddleBinding {
+                device: InputDevice::Keyboard,
+                negative: KeyCode::ArrowLeft,
+                positive: KeyCode::ArrowRight,
+                gamepad: 0,
+            },
+            left: PaddleBinding {
+                device: InputDevice::Keyboard,
+                negative: KeyCode::KeyS,
+                positive: KeyCode::KeyW,
+                gamepad: 0,
+            },
+            right: PaddleBinding {
+                device: InputDevice::Keyboard,
+                negative: KeyCode::ArrowDown,
+                positive: KeyCode::ArrowUp,
+                gamepad: 1,
+            },
+        }
+    }
+}
+
+impl InputBindings {
+    /// binding of the paddle
+    pub fn get(&self, paddle: Paddle) -> &PaddleBinding {
+        match paddle {
+            Paddle::Bottom => &self.bottom,
+            Paddle::Left => &self.left,
+            Paddle::Right => &self.right,
+        }
+    }
+
+    pub fn get_mut(&mut self, paddle: Paddle) -> &mut PaddleBinding {
+        match paddle {
+            Paddle::Bottom => &mut self.bottom,
+            Paddle::Left => &mut self.left,
+            Paddle::Right => &mut self.right,
+        }
+    }
+}
+
+/// plugin for the player input
+pub struct InputPlugin;
+impl Plugin for InputPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputBindings>()
+            .add_systems(Startup, load_bindings);
+    }
+}
+
+/// reads the saved bindings, without a file the default bindings are used
+fn load_bindings(mut commands: Commands) {
+    match load_config::<InputBindings>(BINDINGS_FILE) {
+        Ok(Some(bindings)) => commands.insert_resource(bindings),
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// writes the bindings into the config directory
+pub fn save_bindings(bindings: &InputBindings) -> Result<(), ConfigError> {
+    save_config(BINDINGS_FILE, bindings)
+}
+
+/// sets the intent of the paddles which are not moved by the cpu or a remote player
+/// mouse and touch move the paddle to the pointer, the other devices give a direction
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn player_intent(
+    bindings: Res<InputBindings>,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<(Entity, &Gamepad)>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform)>,
+    touches: Res<Touches>,
+    mut paddles: Query<
+        (&Position, &Paddle, &mut PaddleIntent),
+        (Without<CpuPlayer>, Without<RemotePlayer>),
+    >,
+    time: Res<Time>,
+) {
+    // the pointer positions in the world
+    let to_world = |viewport_position: Vec2| {
+        let (camera, camera_transform) = camera.single().ok()?;
+        camera
+            .viewport_to_world_2d(camera_transform, viewport_position)
+            .ok()
+    };
+    let cursor = window
+        .single()
+        .ok()
+        .and_then(|window| window.cursor_position())
+        .and_then(&to_world);
+    let touch = touches.first_pressed_position().and_then(&to_world);
+
+    for (position, paddle, mut intent) in &mut paddles {
+        let binding = bindings.get(*paddle);
+
+        intent.0 = match binding.device {
+            InputDevice::Keyboard => {
+                let mut direction = 0.0;
+                if keyboard_input.pressed(binding.negative) {
+                    direction -= 1.0;
+                }
+                if keyboard_input.pressed(binding.positive) {
+                    direction += 1.0;
+                }
+                direction
+            }
+            // the order of the query changes after a reconnect, the order of the entities not
+            InputDevice::Gamepad => gamepads
+                .iter()
+                .sort::<Entity>()
+                .nth(binding.gamepad)
+                .map_or(0.0, |(_, gamepad)| {
+                    let stick = paddle.along(gamepad.left_stick());
+                    let stick = if stick.abs() < STICK_DEADZONE {
+                        0.0
+                    } else {
+                        stick
+                    };
+                    (stick + paddle.along(gamepad.dpad())).clamp(-1.0, 1.0)
+                }),
+            // without a pointer the paddle stays where it is
+            InputDevice::Mouse | InputDevice::Touch => {
+                let pointer = if binding.device == InputDevice::Mouse {
+                    cursor
+                } else {
+                    touch
+                };
+                pointer.map_or(0.0, |pointer| {
+                    PaddleIntent::towards(
+                        paddle.along(pointer),
+                        paddle.along(position.current),
+                        time.delta_secs(),
+                    )
+                    .0
+                })
+            }
+        };
+    }
+}
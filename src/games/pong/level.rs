@@ -0,0 +1,227 @@
+/// level files of the pong game
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::games::pong::{
+    bricks::BrickLevel,
+    game::{BALL_SPEED, PADDLE_SIZE, PongMode, PongState, Wall},
+    replay::ReplayPlayer,
+    rules::MatchRules,
+};
+
+/// level file of the classic mode
+const CLASSIC_LEVEL: &str = "levels/classic.level.ron";
+/// level file of the versus mode
+const VERSUS_LEVEL: &str = "levels/versus.level.ron";
+/// level files of the breakout mode in the order they are played
+const BREAKOUT_LEVELS: [&str; 3] = [
+    "levels/breakout_1.level.ron",
+    "levels/breakout_2.level.ron",
+    "levels/breakout_3.level.ron",
+];
+
+/// arena parameters and bricks of one level
+#[derive(Asset, TypePath, Serialize, Deserialize, Clone, Debug)]
+pub struct PongLevel {
+    /// the mode the level is made for, breakout levels need bricks
+    pub mode: PongMode,
+    pub ball_speed: f32,
+    /// length of the paddle, for the side paddles of the versus mode it is the height
+    pub paddle_width: f32,
+    /// walls which end the rally on contact, all others bounce the ball
+    pub exit_walls: Vec<Wall>,
+    /// every digit is a brick with that many hit points, '.' is empty
+    #[serde(default)]
+    pub bricks: Vec<String>,
+}
+
+impl Default for PongLevel {
+    fn default() -> Self {
+        Self {
+            mode: PongMode::Classic,
+            ball_speed: BALL_SPEED,
+            paddle_width: PADDLE_SIZE.x,
+            exit_walls: vec![Wall::Bottom],
+            bricks: Vec::new(),
+        }
+    }
+}
+
+impl PongLevel {
+    /// the level of the mode if its file is not loaded
+    pub fn for_mode(mode: PongMode) -> Self {
+        match mode {
+            PongMode::Classic => Self::default(),
+            PongMode::Breakout => Self {
+                mode,
+                bricks: vec!["1111111111".to_string(); 3],
+                ..default()
+            },
+            PongMode::Versus => Self {
+                mode,
+                exit_walls: vec![Wall::Left, Wall::Right],
+                ..default()
+            },
+        }
+    }
+
+    /// true if the layout has at least one brick with hit points
+    pub fn has_bricks(&self) -> bool {
+        self.bricks
+            .iter()
+            .flat_map(|row| row.chars())
+            .any(|cell| cell.to_digit(10).is_some_and(|hit_points| hit_points > 0))
+    }
+
+    /// reads a level file, a breakout level without bricks would be cleared at once
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, PongLevelLoaderError> {
+        let level = ron::de::from_bytes::<PongLevel>(bytes)?;
+        if level.mode == PongMode::Breakout && !level.has_bricks() {
+            return Err(PongLevelLoaderError::NoBricks);
+        }
+        Ok(level)
+    }
+}
+
+/// handles of all level files
+#[derive(Resource)]
+pub struct PongLevels {
+    classic: Handle<PongLevel>,
+    breakout: Vec<Handle<PongLevel>>,
+    versus: Handle<PongLevel>,
+}
+
+impl PongLevels {
+    /// the same level for every mode
+    pub fn single(level: Handle<PongLevel>) -> Self {
+        Self {
+            classic: level.clone(),
+            breakout: vec![level.clone()],
+            versus: level,
+        }
+    }
+
+    /// handle of the level for the mode, the breakout levels start again after the last one
+    pub fn handle(&self, mode: PongMode, level: usize) -> &Handle<PongLevel> {
+        match mode {
+            PongMode::Classic => &self.classic,
+            PongMode::Breakout => &self.breakout[level % self.breakout.len()],
+            PongMode::Versus => &self.versus,
+        }
+    }
+}
+
+/// the level of the running game
+#[derive(Resource, Default, Deref)]
+pub struct CurrentLevel(pub PongLevel);
+
+/// reads a level file in the RON format
+#[derive(Default)]
+struct PongLevelLoader;
+
+/// errors while loading a level file
+#[derive(Debug, Error)]
+pub enum PongLevelLoaderError {
+    #[error("Could not load level: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse level: {0}")]
+    Ron(#[from] ron::error::SpannedError),
+    #[error("Breakout level has no bricks")]
+    NoBricks,
+}
+
+impl AssetLoader for PongLevelLoader {
+    type Asset = PongLevel;
+    type Settings = ();
+    type Error = PongLevelLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        // a rejected level is not loaded, so the fallback level of the mode is used
+        PongLevel::from_ron(&bytes)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+/// plugin for loading the level files
+pub struct LevelPlugin;
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<PongLevel>()
+            .init_asset_loader::<PongLevelLoader>()
+            .init_resource::<CurrentLevel>()
+            .add_systems(
+                Startup,
+                load_levels.run_if(not(resource_exists::<PongLevels>)),
+            )
+            .add_systems(
+                Update,
+                reload_level
+                    .run_if(in_state(PongState::Game))
+                    .run_if(not(resource_exists::<ReplayPlayer>)),
+            );
+    }
+}
+
+/// starts loading all level files
+/// levels which were inserted before, like in the tests, are kept
+fn load_levels(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(PongLevels {
+        classic: asset_server.load(CLASSIC_LEVEL),
+        breakout: BREAKOUT_LEVELS
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect(),
+        versus: asset_server.load(VERSUS_LEVEL),
+    });
+}
+
+/// selects the level for the mode and level number
+/// falls back to the default level of the mode if the file is not loaded
+pub fn select_level(
+    commands: &mut Commands,
+    levels: &PongLevels,
+    level_assets: &Assets<PongLevel>,
+    mode: PongMode,
+    brick_level: &BrickLevel,
+    rules: &MatchRules,
+) -> PongLevel {
+    let mut level = level_assets
+        .get(levels.handle(mode, brick_level.0))
+        .cloned()
+        .unwrap_or_else(|| PongLevel::for_mode(mode));
+    rules.apply(&mut level);
+    commands.insert_resource(CurrentLevel(level.clone()));
+    level
+}
+
+/// restarts the level if its file was changed while playing
+/// replays keep the level of the recording
+fn reload_level(
+    mut level_events: EventReader<AssetEvent<PongLevel>>,
+    levels: Res<PongLevels>,
+    mode: Res<PongMode>,
+    brick_level: Res<BrickLevel>,
+    mut pong_state: ResMut<NextState<PongState>>,
+) {
+    let current = levels.handle(*mode, brick_level.0).id();
+
+    for event in level_events.read() {
+        if event.is_modified(current) {
+            pong_state.set(PongState::Loading);
+        }
+    }
+}
@@ -1 +1,23 @@
+pub mod ai;
+pub mod bricks;
+pub mod countdown;
+pub mod effects;
+pub mod end_screen;
 pub mod game;
+#[cfg(test)]
+pub mod headless;
+pub mod highscore;
+pub mod input;
+pub mod level;
+pub mod network;
+pub mod pause_menu;
+pub mod physics;
+pub mod power_ups;
+pub mod replay;
+pub mod rules;
+pub mod score;
+pub mod sound;
+pub mod versus;
+
+#[cfg(test)]
+mod tests;
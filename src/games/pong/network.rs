@@ -0,0 +1,592 @@
+/// versus games between two machines in the local network
+/// the host runs the simulation and sends its state to the client,
+/// the client sends its inputs and predicts its own paddle until the host has applied them
+use std::{
+    collections::VecDeque,
+    io::ErrorKind,
+    net::{Ipv4Addr, Ipv6Addr, SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::enums::GameState;
+use crate::games::pong::{
+    ai::{Controller, PaddleControllers},
+    game::{
+        Ball, GameSurface, Paddle, PaddleIntent, PongSimulation, PongState, Position, Velocity,
+        game_setup, new_game, paddle_step, pong_setup, simulation_running,
+    },
+    input::player_intent,
+    replay::{INTENT_STEPS, ReplayRecorder, record_intent},
+    rules::MatchRules,
+    versus::VersusScore,
+};
+
+// network constanten
+pub const DEFAULT_PORT: u16 = 7777;
+/// the connection is lost after this long without a message
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// the client says hello this often, until it is accepted and afterwards to stay connected
+const HELLO_INTERVAL: f32 = 0.5;
+/// every input message repeats the newest inputs, so a lost message does not matter
+const REDUNDANT_INPUTS: usize = 8;
+/// inputs the host keeps for the next steps, older ones are skipped
+const MAX_QUEUED_INPUTS: usize = 4;
+/// steps without a new input of the client until the host stops its paddle
+const MAX_INPUT_GAP: u32 = 12;
+/// inputs the client keeps for the prediction, older ones are dropped
+const MAX_PENDING_INPUTS: usize = 256;
+const MAX_MESSAGE_SIZE: usize = 1024;
+
+/// who runs the simulation
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum NetRole {
+    Host,
+    Client,
+}
+
+/// paddles moved by the player on the other machine need this
+#[derive(Component)]
+pub struct RemotePlayer;
+
+/// the state of the game on the host, sent to the client after every frame
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct HostState {
+    /// counts the sent states, older states are ignored
+    sequence: u32,
+    /// tick of the last input of the client the host has applied
+    ack: u32,
+    phase: PongState,
+    /// position and velocity of the ball
+    ball: Option<(Vec2, Vec2)>,
+    paddles: Vec<(Paddle, Vec2)>,
+    /// goals of the left and the right player
+    goals: (u32, u32),
+}
+
+/// everything host and client send each other
+#[derive(Serialize, Deserialize, Debug)]
+enum NetMessage {
+    /// the client wants to play, the host answers with a welcome and its rules
+    Hello,
+    Welcome(MatchRules),
+    /// the newest inputs of the client, the last one belongs to the tick
+    Inputs {
+        tick: u32,
+        intents: Vec<i8>,
+    },
+    State(HostState),
+    /// the other side left the game
+    Bye,
+}
+
+/// errors of the network game
+#[derive(Debug, Error)]
+pub enum NetworkError {
+    #[error("Network error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not read network message: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not write network message: {0}")]
+    Write(#[from] ron::Error),
+}
+
+/// the socket and the state of a network game
+#[derive(Resource)]
+pub struct NetConnection {
+    socket: UdpSocket,
+    pub role: NetRole,
+    /// the other machine, the host knows it after the first hello
+    pub peer: Option<SocketAddr>,
+    /// the host has accepted the client
+    pub connected: bool,
+    /// the other side has left the game
+    pub closed: bool,
+    /// client: the rules of the host, they come with the welcome
+    pub host_rules: Option<MatchRules>,
+    /// client: the rules of the player, they are set again when the game ends
+    player_rules: Option<MatchRules>,
+    last_received: Instant,
+    hello_timer: Timer,
+    /// host: sent states, client: own simulation steps
+    tick: u32,
+    /// host: inputs of the client which are not applied yet
+    remote_inputs: VecDeque<(u32, i8)>,
+    /// host: the last applied input of the client, used again until the next one arrives
+    last_input: (u32, i8),
+    /// host: steps since the last input of the client was applied
+    input_gap: u32,
+    /// client: own inputs the host has not applied yet
+    pending_inputs: VecDeque<(u32, i8)>,
+    /// client: newest state of the host which is not applied yet
+    host_state: Option<HostState>,
+    /// client: sequence of the newest received state
+    last_sequence: u32,
+    /// client: the phase of the game on the host
+    host_phase: PongState,
+    /// client: the game was paused because the host paused it
+    paused_by_host: bool,
+}
+
+impl NetConnection {
+    /// waits on the address for a client
+    pub fn host(address: SocketAddr) -> Result<Self, NetworkError> {
+        Self::new(UdpSocket::bind(address)?, NetRole::Host, None)
+    }
+
+    /// asks the host at the address for a game
+    pub fn join(address: SocketAddr) -> Result<Self, NetworkError> {
+        let local: SocketAddr = if address.is_ipv4() {
+            (Ipv4Addr::UNSPECIFIED, 0).into()
+        } else {
+            (Ipv6Addr::UNSPECIFIED, 0).into()
+        };
+        let connection = Self::new(UdpSocket::bind(local)?, NetRole::Client, Some(address))?;
+        connection.send(&NetMessage::Hello)?;
+        Ok(connection)
+    }
+
+    fn new(
+        socket: UdpSocket,
+        role: NetRole,
+        peer: Option<SocketAddr>,
+    ) -> Result<Self, NetworkError> {
+        socket.set_nonblocking(true)?;
+        Ok(Self {
+            socket,
+            role,
+            peer,
+            connected: false,
+            closed: false,
+            host_rules: None,
+            player_rules: None,
+            last_received: Instant::now(),
+            hello_timer: Timer::from_seconds(HELLO_INTERVAL, TimerMode::Repeating),
+            tick: 0,
+            remote_inputs: VecDeque::new(),
+            last_input: (0, 0),
+            input_gap: 0,
+            pending_inputs: VecDeque::new(),
+            host_state: None,
+            last_sequence: 0,
+            host_phase: PongState::Disabled,
+            paused_by_host: false,
+        })
+    }
+
+    pub fn local_addr(&self) -> Result<SocketAddr, NetworkError> {
+        Ok(self.socket.local_addr()?)
+    }
+
+    /// every machine moves its own paddle, the host the left and the client the right one
+    pub fn controllers(&self) -> PaddleControllers {
+        let (left, right) = match self.role {
+            NetRole::Host => (Controller::Human, Controller::Remote),
+            NetRole::Client => (Controller::Remote, Controller::Human),
+        };
+        PaddleControllers {
+            left,
+            right,
+            ..default()
+        }
+    }
+
+    fn send(&self, message: &NetMessage) -> Result<(), NetworkError> {
+        let Some(peer) = self.peer else {
+            return Ok(());
+        };
+        let text = ron::to_string(message)?;
+        self.socket.send_to(text.as_bytes(), peer)?;
+        Ok(())
+    }
+
+    /// udp messages can get lost anyway, so a failed send only gets printed
+    fn send_or_print(&self, message: &NetMessage) {
+        if let Err(e) = self.send(message) {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// all messages which arrived since the last call
+    fn receive(&self) -> Vec<(NetMessage, SocketAddr)> {
+        let mut messages = Vec::new();
+        let mut buffer = [0; MAX_MESSAGE_SIZE];
+        loop {
+            match self.socket.recv_from(&mut buffer) {
+                Ok((length, sender)) => match ron::de::from_bytes(&buffer[..length]) {
+                    Ok(message) => messages.push((message, sender)),
+                    Err(e) => eprintln!("{}", NetworkError::from(e)),
+                },
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break,
+                // some systems report a closed port of the peer on the next receive
+                Err(e) if e.kind() == ErrorKind::ConnectionReset => continue,
+                Err(e) => {
+                    eprintln!("{}", NetworkError::from(e));
+                    break;
+                }
+            }
+        }
+        messages
+    }
+
+    /// queues the inputs of the client which are newer than the queued ones
+    fn queue_inputs(&mut self, tick: u32, intents: &[i8]) {
+        let newest = self
+            .remote_inputs
+            .back()
+            .map_or(self.last_input.0, |(tick, _)| *tick);
+        let first = (tick + 1).saturating_sub(intents.len() as u32);
+        for (input_tick, intent) in (first..).zip(intents) {
+            if input_tick > newest {
+                self.remote_inputs.push_back((input_tick, *intent));
+            }
+        }
+    }
+}
+
+/// plugin for versus games in the local network
+/// all systems do nothing without a NetConnection
+pub struct NetworkPlugin;
+impl Plugin for NetworkPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PreUpdate,
+            receive_messages.run_if(resource_exists::<NetConnection>),
+        )
+        .add_systems(
+            Update,
+            (
+                send_hello.run_if(is_network_client),
+                (
+                    send_state.run_if(is_network_host),
+                    follow_host_phase.run_if(is_network_client),
+                    check_connection.run_if(resource_exists::<NetConnection>),
+                )
+                    .run_if(in_state(GameState::Pong)),
+            ),
+        )
+        .add_systems(
+            OnEnter(GameState::Pong),
+            use_host_rules.run_if(is_network_client).before(game_setup),
+        )
+        .add_systems(
+            OnEnter(PongState::Loading),
+            remove_recorder.run_if(is_network_client).before(pong_setup),
+        )
+        .add_systems(
+            OnExit(GameState::Pong),
+            leave_game.run_if(resource_exists::<NetConnection>),
+        )
+        .add_systems(
+            FixedUpdate,
+            apply_host_state
+                .run_if(is_network_client)
+                .before(PongSimulation),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                remote_intent.run_if(is_network_host),
+                send_inputs.run_if(is_network_client),
+            )
+                .after(player_intent)
+                .before(record_intent)
+                .run_if(simulation_running),
+        )
+        .add_systems(
+            FixedUpdate,
+            predict_ball
+                .after(PongSimulation)
+                .run_if(is_network_client)
+                .run_if(simulation_running),
+        );
+    }
+}
+
+/// run condition for the systems of the host
+pub fn is_network_host(connection: Option<Res<NetConnection>>) -> bool {
+    connection.is_some_and(|connection| connection.role == NetRole::Host)
+}
+
+/// run condition for the systems of the client
+pub fn is_network_client(connection: Option<Res<NetConnection>>) -> bool {
+    connection.is_some_and(|connection| connection.role == NetRole::Client)
+}
+
+/// handles the arrived messages, the host accepts the first client which says hello
+fn receive_messages(mut connection: ResMut<NetConnection>, rules: Res<MatchRules>) {
+    for (message, sender) in connection.receive() {
+        if connection.role == NetRole::Host
+            && connection.peer.is_none()
+            && matches!(message, NetMessage::Hello)
+        {
+            connection.peer = Some(sender);
+        }
+        if connection.peer != Some(sender) {
+            continue;
+        }
+        connection.last_received = Instant::now();
+
+        match (connection.role, message) {
+            // the welcome can get lost, so every hello gets one
+            (NetRole::Host, NetMessage::Hello) => {
+                connection.connected = true;
+                connection.send_or_print(&NetMessage::Welcome(rules.clone()));
+            }
+            (NetRole::Client, NetMessage::Welcome(rules)) => {
+                connection.connected = true;
+                connection.host_rules = Some(rules);
+            }
+            (NetRole::Host, NetMessage::Inputs { tick, intents }) => {
+                connection.queue_inputs(tick, &intents);
+            }
+            (NetRole::Client, NetMessage::State(state))
+                if state.sequence > connection.last_sequence =>
+            {
+                connection.last_sequence = state.sequence;
+                connection.host_phase = state.phase;
+                connection.host_state = Some(state);
+            }
+            (_, NetMessage::Bye) => connection.closed = true,
+            _ => {}
+        }
+    }
+}
+
+/// the client says hello until it is accepted,
+/// afterwards the hello keeps the connection alive while the client sends no inputs
+fn send_hello(mut connection: ResMut<NetConnection>, time: Res<Time<Real>>) {
+    if connection.hello_timer.tick(time.delta()).just_finished() {
+        connection.send_or_print(&NetMessage::Hello);
+    }
+}
+
+/// sends the ball, the paddles and the goals of the host to the client
+fn send_state(
+    mut connection: ResMut<NetConnection>,
+    balls: Query<(&Position, &Velocity), With<Ball>>,
+    paddles: Query<(&Position, &Paddle)>,
+    score: Res<VersusScore>,
+    pong_state: Res<State<PongState>>,
+) {
+    if !connection.connected {
+        return;
+    }
+    connection.tick += 1;
+    let state = HostState {
+        sequence: connection.tick,
+        ack: connection.last_input.0,
+        phase: *pong_state.get(),
+        ball: balls
+            .iter()
+            .next()
+            .map(|(position, velocity)| (position.current, velocity.0)),
+        paddles: paddles
+            .iter()
+            .map(|(position, paddle)| (*paddle, position.current))
+            .collect(),
+        goals: (score.left, score.right),
+    };
+    connection.send_or_print(&NetMessage::State(state));
+}
+
+/// the paddle of the client moves with one of its inputs in every step
+/// without a new input the last one is used again, after a longer gap the paddle stops,
+/// so a paused or lost client does not move on forever
+fn remote_intent(
+    mut connection: ResMut<NetConnection>,
+    mut paddles: Query<&mut PaddleIntent, With<RemotePlayer>>,
+) {
+    // a client which runs faster than the host would get further and further behind
+    while connection.remote_inputs.len() > MAX_QUEUED_INPUTS {
+        connection.remote_inputs.pop_front();
+    }
+    if let Some(input) = connection.remote_inputs.pop_front() {
+        connection.last_input = input;
+        connection.input_gap = 0;
+    } else {
+        connection.input_gap += 1;
+    }
+    let steps = if connection.input_gap > MAX_INPUT_GAP {
+        0
+    } else {
+        connection.last_input.1
+    };
+    for mut intent in &mut paddles {
+        intent.0 = steps as f32 / INTENT_STEPS;
+    }
+}
+
+/// sends the intent of the own paddle and keeps it for the prediction
+/// the intent is rounded like on the host, so the prediction moves the paddle the same way
+fn send_inputs(
+    mut connection: ResMut<NetConnection>,
+    mut paddles: Query<&mut PaddleIntent, Without<RemotePlayer>>,
+) {
+    let Ok(mut intent) = paddles.single_mut() else {
+        return;
+    };
+    let steps = (intent.0.clamp(-1.0, 1.0) * INTENT_STEPS).round() as i8;
+    intent.0 = steps as f32 / INTENT_STEPS;
+
+    connection.tick += 1;
+    let tick = connection.tick;
+    connection.pending_inputs.push_back((tick, steps));
+    if connection.pending_inputs.len() > MAX_PENDING_INPUTS {
+        connection.pending_inputs.pop_front();
+    }
+    let skip = connection
+        .pending_inputs
+        .len()
+        .saturating_sub(REDUNDANT_INPUTS);
+    let intents = connection
+        .pending_inputs
+        .iter()
+        .skip(skip)
+        .map(|(_, steps)| *steps)
+        .collect();
+    connection.send_or_print(&NetMessage::Inputs { tick, intents });
+}
+
+/// takes the ball, the paddles and the goals from the newest state of the host
+/// the own paddle moves again with the inputs the host has not applied yet
+fn apply_host_state(
+    mut connection: ResMut<NetConnection>,
+    mut balls: Query<(&mut Position, &mut Velocity), With<Ball>>,
+    mut paddles: Query<(&mut Position, &Transform, &Paddle, Has<RemotePlayer>), Without<Ball>>,
+    mut score: ResMut<VersusScore>,
+    screen: Option<Res<GameSurface>>,
+    time: Res<Time>,
+) {
+    let Some(screen) = screen else {
+        return;
+    };
+    let Some(state) = connection.host_state.take() else {
+        return;
+    };
+    connection
+        .pending_inputs
+        .retain(|(tick, _)| *tick > state.ack);
+
+    if let (Some((position, velocity)), Ok((mut ball_position, mut ball_velocity))) =
+        (state.ball, balls.single_mut())
+    {
+        ball_position.current = position;
+        ball_velocity.0 = velocity;
+    }
+    for (mut position, transform, paddle, remote) in &mut paddles {
+        let Some((_, host_position)) = state.paddles.iter().find(|(other, _)| other == paddle)
+        else {
+            continue;
+        };
+        position.current = *host_position;
+        if remote {
+            continue;
+        }
+        for (_, steps) in &connection.pending_inputs {
+            position.current = paddle_step(
+                *paddle,
+                position.current,
+                transform.scale,
+                *steps as f32 / INTENT_STEPS,
+                &screen,
+                time.delta_secs(),
+            );
+        }
+    }
+    if (score.left, score.right) != state.goals {
+        (score.left, score.right) = state.goals;
+    }
+}
+
+/// moves the ball on the client until the next state of the host arrives
+fn predict_ball(mut balls: Query<(&mut Position, &Velocity), With<Ball>>, time: Res<Time>) {
+    for (mut position, velocity) in &mut balls {
+        position.current += velocity.0 * time.delta_secs();
+    }
+}
+
+/// the client follows the host into the next game, the pause and to the end screen
+/// a pause of the client is its own, the host goes on and stops the paddle of the client
+fn follow_host_phase(
+    mut commands: Commands,
+    mut connection: ResMut<NetConnection>,
+    pong_state: Res<State<PongState>>,
+    mut next_state: ResMut<NextState<PongState>>,
+    rules: Res<MatchRules>,
+) {
+    match (connection.host_phase, *pong_state.get()) {
+        (PongState::Game, PongState::Loading) => next_state.set(PongState::Game),
+        (PongState::Paused, PongState::Game) => {
+            connection.paused_by_host = true;
+            next_state.set(PongState::Paused);
+        }
+        (PongState::Game, PongState::Paused) if connection.paused_by_host => {
+            connection.paused_by_host = false;
+            next_state.set(PongState::Game);
+        }
+        (PongState::GameOver, PongState::Game | PongState::Paused) => {
+            connection.paused_by_host = false;
+            next_state.set(PongState::GameOver);
+        }
+        (PongState::Loading | PongState::Game, PongState::GameOver) => {
+            new_game(&mut commands, &mut next_state, None, &rules)
+        }
+        _ => {}
+    }
+}
+
+/// the game of the client is not recorded, the host simulated it
+fn remove_recorder(mut commands: Commands) {
+    commands.remove_resource::<ReplayRecorder>();
+}
+
+/// back to the menu when the other side left or sent nothing for too long
+fn check_connection(
+    mut commands: Commands,
+    connection: Res<NetConnection>,
+    mut pong_state: ResMut<NextState<PongState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    if connection.closed {
+        eprintln!("The other player left the game");
+    } else if connection.last_received.elapsed() > TIMEOUT {
+        eprintln!("Lost the connection to the other player");
+    } else {
+        return;
+    }
+    close_connection(&mut commands, &connection);
+    pong_state.set(PongState::Disabled);
+    game_state.set(GameState::Menu);
+}
+
+/// tells the other side that the game is over and closes the socket
+fn leave_game(mut commands: Commands, connection: Res<NetConnection>) {
+    if !connection.closed {
+        connection.send_or_print(&NetMessage::Bye);
+    }
+    close_connection(&mut commands, &connection);
+}
+
+/// the client plays with the rules of the host and keeps its own rules for later
+fn use_host_rules(
+    mut commands: Commands,
+    mut connection: ResMut<NetConnection>,
+    rules: Res<MatchRules>,
+) {
+    if let Some(host_rules) = connection.host_rules.clone() {
+        connection.player_rules = Some(rules.clone());
+        commands.insert_resource(host_rules);
+    }
+}
+
+/// closes the socket, the client gets its own rules back
+fn close_connection(commands: &mut Commands, connection: &NetConnection) {
+    if let Some(rules) = &connection.player_rules {
+        commands.insert_resource(rules.clone());
+    }
+    commands.remove_resource::<NetConnection>();
+}
@@ -0,0 +1,227 @@
+/// the pause menu of the pong game
+use bevy::prelude::*;
+
+use crate::enums::GameState;
+use crate::functions::despawn_screen;
+use crate::games::pong::{
+    countdown::countdown_text,
+    game::{PongState, new_game},
+    replay::ReplayPlayer,
+    rules::MatchRules,
+};
+use crate::menus::{NORMAL_BUTTON, button_system, menu_button_node};
+
+// pause constanten
+/// seconds from resume until the game goes on
+const RESUME_COUNTDOWN: f32 = 3.0;
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+
+/// all elements on the pause screen need this
+#[derive(Component)]
+struct OnPauseScreen;
+
+/// the text of the resume countdown
+#[derive(Component)]
+struct ResumeCountdownText;
+
+/// button actions of the pause menu
+#[derive(Component)]
+enum PauseButtonAction {
+    Resume,
+    Restart,
+    Quit,
+}
+
+#[derive(Resource, Deref, DerefMut)]
+/// timer from resume until the game goes on
+struct ResumeTimer(Timer);
+
+/// plugin for pausing the pong game
+pub struct PausePlugin;
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(PongState::Paused), pause_menu_setup)
+            .add_systems(
+                OnExit(PongState::Paused),
+                (despawn_screen::<OnPauseScreen>, remove_resume_timer),
+            )
+            .add_systems(Update, pause_game.run_if(in_state(PongState::Game)))
+            .add_systems(
+                Update,
+                (
+                    button_system,
+                    pause_menu_action,
+                    resume_key.run_if(not(resource_exists::<ResumeTimer>)),
+                    resume_countdown.run_if(resource_exists::<ResumeTimer>),
+                )
+                    .run_if(in_state(PongState::Paused)),
+            );
+    }
+}
+
+/// true if Escape, P or the start button of a gamepad was pressed
+fn pause_pressed(keyboard_input: &ButtonInput<KeyCode>, gamepads: &Query<&Gamepad>) -> bool {
+    keyboard_input.any_just_pressed([KeyCode::Escape, KeyCode::KeyP])
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::Start))
+}
+
+/// change PongState::Game to PongState::Paused
+/// the simulation only runs in PongState::Game, so it stops at once
+fn pause_game(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut pong_state: ResMut<NextState<PongState>>,
+) {
+    if pause_pressed(&keyboard_input, &gamepads) {
+        pong_state.set(PongState::Paused);
+    }
+}
+
+/// spawns the pause menu over the game
+fn pause_menu_setup(mut commands: Commands) {
+    let button_node = menu_button_node();
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        BackgroundColor(OVERLAY_COLOR),
+        OnPauseScreen,
+        children![(
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (
+                    Text::new("Paused"),
+                    TextFont {
+                        font_size: 60.0,
+                        ..default()
+                    },
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    PauseButtonAction::Resume,
+                    children![Text::new("Resume")]
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    PauseButtonAction::Restart,
+                    children![Text::new("Restart")]
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    PauseButtonAction::Quit,
+                    children![Text::new("Quit to menu")]
+                )
+            ]
+        )],
+    ));
+}
+
+/// replaces the pause menu with the countdown until the game goes on
+fn start_resume(commands: &mut Commands, screen: &Query<Entity, With<OnPauseScreen>>) {
+    for entity in screen {
+        commands.entity(entity).despawn();
+    }
+    commands.insert_resource(ResumeTimer(Timer::from_seconds(
+        RESUME_COUNTDOWN,
+        TimerMode::Once,
+    )));
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        OnPauseScreen,
+        children![(
+            Text::new(countdown_text(RESUME_COUNTDOWN)),
+            TextFont {
+                font_size: 80.0,
+                ..default()
+            },
+            ResumeCountdownText,
+        )],
+    ));
+}
+
+/// resumes, restarts or quits the game
+#[allow(clippy::type_complexity)]
+fn pause_menu_action(
+    interaction_query: Query<
+        (&Interaction, &PauseButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    screen: Query<Entity, With<OnPauseScreen>>,
+    mut commands: Commands,
+    mut pong_state: ResMut<NextState<PongState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+    mut player: Option<ResMut<ReplayPlayer>>,
+    rules: Res<MatchRules>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            match action {
+                PauseButtonAction::Resume => start_resume(&mut commands, &screen),
+                PauseButtonAction::Restart => new_game(
+                    &mut commands,
+                    &mut pong_state,
+                    player.as_deref_mut(),
+                    &rules,
+                ),
+                PauseButtonAction::Quit => {
+                    pong_state.set(PongState::Disabled);
+                    game_state.set(GameState::Menu);
+                }
+            }
+        }
+    }
+}
+
+/// the pause keys resume the game like the resume button
+fn resume_key(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    screen: Query<Entity, With<OnPauseScreen>>,
+    mut commands: Commands,
+) {
+    if pause_pressed(&keyboard_input, &gamepads) {
+        start_resume(&mut commands, &screen);
+    }
+}
+
+/// counts down and changes PongState::Paused back to PongState::Game
+/// the real time is used, so a paused or fast replay does not change the countdown
+fn resume_countdown(
+    time: Res<Time<Real>>,
+    mut timer: ResMut<ResumeTimer>,
+    mut text: Single<&mut Text, With<ResumeCountdownText>>,
+    mut pong_state: ResMut<NextState<PongState>>,
+) {
+    if timer.tick(time.delta()).finished() {
+        pong_state.set(PongState::Game);
+    }
+    text.0 = countdown_text(timer.remaining_secs());
+}
+
+/// the next pause starts with the menu again
+fn remove_resume_timer(mut commands: Commands) {
+    commands.remove_resource::<ResumeTimer>();
+}
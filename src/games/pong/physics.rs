@@ -0,0 +1,251 @@
+/// swept collision of the ball, so fast balls can not fly through thin objects
+use bevy::{math::bounding::Aabb2d, prelude::*};
+
+/// first contact of a moving circle with a box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit {
+    /// share of the movement until the contact, from 0.0 to 1.0
+    pub time: f32,
+    /// normal of the box surface at the contact point
+    pub normal: Vec2,
+}
+
+/// finds the first contact of a circle which moves by motion with the box
+/// the box grows by the radius so the center of the circle can be traced as a ray,
+/// at the corners of the grown box the ray is traced against a circle around the corner
+pub fn sweep_circle_aabb(center: Vec2, radius: f32, motion: Vec2, aabb: Aabb2d) -> Option<Hit> {
+    // already overlapping at the start, only a hit if the circle moves further in
+    let closest = aabb.closest_point(center);
+    let offset = center - closest;
+    if offset.length_squared() < radius * radius {
+        let normal = offset.try_normalize()?;
+        return (motion.dot(normal) < 0.0).then_some(Hit { time: 0.0, normal });
+    }
+
+    let grown_min = aabb.min - Vec2::splat(radius);
+    let grown_max = aabb.max + Vec2::splat(radius);
+
+    // slab test against the grown box
+    let mut entry = f32::NEG_INFINITY;
+    let mut exit = f32::INFINITY;
+    let mut normal = Vec2::ZERO;
+    for (start, delta, min, max, axis) in [
+        (center.x, motion.x, grown_min.x, grown_max.x, Vec2::X),
+        (center.y, motion.y, grown_min.y, grown_max.y, Vec2::Y),
+    ] {
+        if delta.abs() <= f32::EPSILON {
+            if start < min || start > max {
+                return None;
+            }
+            continue;
+        }
+
+        let to_min = (min - start) / delta;
+        let to_max = (max - start) / delta;
+        let (near, far, near_normal) = if to_min < to_max {
+            (to_min, to_max, -axis)
+        } else {
+            (to_max, to_min, axis)
+        };
+
+        if near > entry {
+            entry = near;
+            normal = near_normal;
+        }
+        exit = exit.min(far);
+    }
+
+    if entry > exit || entry > 1.0 {
+        return None;
+    }
+
+    // the start is inside a corner of the grown box but outside of its round corner
+    if entry < 0.0 {
+        return sweep_circle_point(center, radius, motion, closest);
+    }
+
+    // the grown box has round corners
+    let contact = center + motion * entry;
+    let outside_x = contact.x < aabb.min.x || contact.x > aabb.max.x;
+    let outside_y = contact.y < aabb.min.y || contact.y > aabb.max.y;
+    if outside_x && outside_y {
+        let corner = contact.clamp(aabb.min, aabb.max);
+        return sweep_circle_point(center, radius, motion, corner);
+    }
+
+    Some(Hit {
+        time: entry,
+        normal,
+    })
+}
+
+/// first contact of a circle which moves by motion with a point
+fn sweep_circle_point(center: Vec2, radius: f32, motion: Vec2, point: Vec2) -> Option<Hit> {
+    let offset = center - point;
+    let a = motion.length_squared();
+    let b = 2.0 * motion.dot(offset);
+    let c = offset.length_squared() - radius * radius;
+
+    let discriminant = b * b - 4.0 * a * c;
+    if a <= f32::EPSILON || discriminant < 0.0 {
+        return None;
+    }
+
+    let time = (-b - discriminant.sqrt()) / (2.0 * a);
+    if !(0.0..=1.0).contains(&time) {
+        return None;
+    }
+
+    let normal = (center + motion * time - point).try_normalize()?;
+    Some(Hit { time, normal })
+}
+
+/// velocity after a bounce on a surface with the normal
+pub fn reflect(velocity: Vec2, normal: Vec2) -> Vec2 {
+    velocity - 2.0 * velocity.dot(normal) * normal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::pong::game::MAX_BOUNCES_PER_STEP;
+
+    const RADIUS: f32 = 5.0;
+
+    fn assert_near(actual: Vec2, expected: Vec2) {
+        assert!(
+            actual.distance(expected) < 1e-3,
+            "{actual} is not {expected}"
+        );
+    }
+
+    /// moves the ball for one step like move_ball, returns the end and the number of bounces
+    fn move_with_bounces(
+        mut center: Vec2,
+        mut velocity: Vec2,
+        boxes: &[Aabb2d],
+    ) -> (Vec2, Vec2, usize) {
+        let mut remaining = 1.0;
+        let mut bounces = 0;
+        for _ in 0..MAX_BOUNCES_PER_STEP {
+            let motion = velocity * remaining;
+            let first_hit = boxes
+                .iter()
+                .filter_map(|aabb| sweep_circle_aabb(center, RADIUS, motion, *aabb))
+                .min_by(|first, second| first.time.total_cmp(&second.time));
+            let Some(hit) = first_hit else {
+                center += motion;
+                break;
+            };
+            center += motion * hit.time;
+            remaining *= 1.0 - hit.time;
+            velocity = reflect(velocity, hit.normal);
+            bounces += 1;
+        }
+        (center, velocity, bounces)
+    }
+
+    #[test]
+    fn fast_ball_hits_thin_box() {
+        // the ball moves 500 in one step, the box is only 10 thick
+        let aabb = Aabb2d::new(Vec2::new(105.0, 0.0), Vec2::new(5.0, 100.0));
+        let hit = sweep_circle_aabb(Vec2::ZERO, RADIUS, Vec2::new(500.0, 0.0), aabb).unwrap();
+        assert!((hit.time - 95.0 / 500.0).abs() < 1e-6);
+        assert_eq!(hit.normal, Vec2::NEG_X);
+    }
+
+    #[test]
+    fn exact_corner_hit() {
+        let aabb = Aabb2d::new(Vec2::splat(5.0), Vec2::splat(5.0));
+        let hit = sweep_circle_aabb(Vec2::splat(-5.0), RADIUS, Vec2::splat(10.0), aabb).unwrap();
+        let diagonal = Vec2::ONE.normalize();
+        let distance = Vec2::splat(5.0).length() - RADIUS;
+        assert!((hit.time - distance / Vec2::splat(10.0).length()).abs() < 1e-5);
+        assert_near(hit.normal, -diagonal);
+        assert_near(reflect(Vec2::splat(10.0), hit.normal), Vec2::splat(-10.0));
+    }
+
+    #[test]
+    fn corner_hit_from_inside_the_grown_box() {
+        // the start is inside the grown box, but further than the radius from the corner
+        let aabb = Aabb2d::new(Vec2::splat(5.0), Vec2::splat(5.0));
+        let center = Vec2::splat(-4.0);
+        let motion = Vec2::splat(10.0);
+        let hit = sweep_circle_aabb(center, RADIUS, motion, aabb).unwrap();
+        let distance = center.length() - RADIUS;
+        assert!((hit.time - distance / motion.length()).abs() < 1e-5);
+        assert_near(hit.normal, -Vec2::ONE.normalize());
+
+        // moving past the corner is no hit
+        assert!(sweep_circle_aabb(center, RADIUS, Vec2::new(-10.0, 10.0), aabb).is_none());
+    }
+
+    #[test]
+    fn ball_starting_in_contact() {
+        let aabb = Aabb2d::new(Vec2::new(5.0, 0.0), Vec2::splat(5.0));
+        let touching = Vec2::new(-RADIUS, 0.0);
+
+        // moving in is a hit at once, moving away is none
+        let hit = sweep_circle_aabb(touching, RADIUS, Vec2::new(10.0, 0.0), aabb).unwrap();
+        assert_eq!(
+            hit,
+            Hit {
+                time: 0.0,
+                normal: Vec2::NEG_X
+            }
+        );
+        assert!(sweep_circle_aabb(touching, RADIUS, Vec2::new(-10.0, 0.0), aabb).is_none());
+
+        // the same for a ball which is already a bit inside
+        let overlapping = Vec2::new(-RADIUS + 0.5, 0.0);
+        let hit = sweep_circle_aabb(overlapping, RADIUS, Vec2::new(10.0, 0.0), aabb).unwrap();
+        assert_eq!(
+            hit,
+            Hit {
+                time: 0.0,
+                normal: Vec2::NEG_X
+            }
+        );
+        assert!(sweep_circle_aabb(overlapping, RADIUS, Vec2::new(-10.0, 0.0), aabb).is_none());
+    }
+
+    #[test]
+    fn ball_moving_parallel_to_face() {
+        let aabb = Aabb2d::new(Vec2::splat(5.0), Vec2::splat(5.0));
+        let motion = Vec2::new(0.0, 50.0);
+
+        // a gap to the face is never closed
+        assert!(sweep_circle_aabb(Vec2::new(-6.0, -20.0), RADIUS, motion, aabb).is_none());
+
+        // a ball which slides along the face only grazes it and keeps its velocity
+        let hit = sweep_circle_aabb(Vec2::new(-RADIUS, -20.0), RADIUS, motion, aabb).unwrap();
+        assert_near(hit.normal, Vec2::NEG_X);
+        assert_near(reflect(motion, hit.normal), motion);
+    }
+
+    #[test]
+    fn several_bounces_in_one_step() {
+        // a corner of a right and a top wall
+        let right = Aabb2d::new(Vec2::new(105.0, 0.0), Vec2::new(5.0, 100.0));
+        let top = Aabb2d::new(Vec2::new(0.0, 105.0), Vec2::new(110.0, 5.0));
+
+        let (center, velocity, bounces) =
+            move_with_bounces(Vec2::new(80.0, 70.0), Vec2::splat(100.0), &[right, top]);
+        assert_eq!(bounces, 2);
+        assert_near(center, Vec2::new(10.0, 20.0));
+        assert_near(velocity, Vec2::splat(-100.0));
+    }
+
+    #[test]
+    fn bounces_are_limited_per_step() {
+        // a channel which is only a bit wider than the ball
+        let bottom = Aabb2d::new(Vec2::new(0.0, -11.0), Vec2::new(1000.0, 5.0));
+        let top = Aabb2d::new(Vec2::new(0.0, 11.0), Vec2::new(1000.0, 5.0));
+
+        let (center, _, bounces) =
+            move_with_bounces(Vec2::ZERO, Vec2::new(100.0, 100.0), &[bottom, top]);
+        assert_eq!(bounces, MAX_BOUNCES_PER_STEP);
+        assert!(center.y.abs() <= 1.0 + 1e-3);
+        assert!(center.x < 100.0);
+    }
+}
@@ -0,0 +1,327 @@
+/// power-ups which drop from hit objects and are caught with the paddle
+use bevy::{
+    math::bounding::{Aabb2d, IntersectsVolume},
+    prelude::*,
+};
+use rand::Rng;
+
+use crate::games::pong::{
+    game::{
+        Ball, GameSurface, ObjectHit, OnGameScreen, PADDLE_COLOR, Paddle, PaddleIntent, PongRng,
+        PongSimulation, Position, Velocity, ball_bundle, bounding_box, paddle_bounce,
+        simulation_running,
+    },
+    level::CurrentLevel,
+    network::NetConnection,
+};
+
+// power-up constanten
+const POWER_UP_SIZE: f32 = 30.0;
+const POWER_UP_SPEED: f32 = 200.0;
+/// chance that a bounce off an object drops a power-up
+const DROP_CHANCE: f64 = 0.1;
+/// seconds a caught power-up lasts
+pub const POWER_UP_DURATION: f32 = 10.0;
+pub const WIDE_PADDLE_FACTOR: f32 = 1.5;
+const NARROW_PADDLE_FACTOR: f32 = 0.6;
+pub const SLOW_BALL_FACTOR: f32 = 0.5;
+/// seconds a sticky paddle holds the ball before it is launched again
+pub const STICKY_HOLD_TIME: f32 = 1.0;
+const STICKY_PADDLE_COLOR: Color = Color::srgb(0.4, 1.0, 0.4);
+/// angle between the old ball and the new balls of the multi-ball
+const MULTI_BALL_ANGLE: f32 = 20.0_f32.to_radians();
+const MAX_BALLS: usize = 8;
+
+/// the different power-ups, a falling power-up has this as component
+#[derive(Component, Clone, Copy, Eq, PartialEq, Debug)]
+pub enum PowerUp {
+    MultiBall,
+    WidePaddle,
+    NarrowPaddle,
+    SlowBall,
+    StickyPaddle,
+}
+
+impl PowerUp {
+    const ALL: [PowerUp; 5] = [
+        PowerUp::MultiBall,
+        PowerUp::WidePaddle,
+        PowerUp::NarrowPaddle,
+        PowerUp::SlowBall,
+        PowerUp::StickyPaddle,
+    ];
+
+    fn color(self) -> Color {
+        match self {
+            PowerUp::MultiBall => Color::srgb(1.0, 0.3, 0.3),
+            PowerUp::WidePaddle => Color::srgb(0.3, 0.6, 1.0),
+            PowerUp::NarrowPaddle => Color::srgb(0.6, 0.3, 0.8),
+            PowerUp::SlowBall => Color::srgb(1.0, 0.9, 0.3),
+            PowerUp::StickyPaddle => STICKY_PADDLE_COLOR,
+        }
+    }
+
+    /// letter on the falling power-up
+    fn label(self) -> &'static str {
+        match self {
+            PowerUp::MultiBall => "M",
+            PowerUp::WidePaddle => "W",
+            PowerUp::NarrowPaddle => "N",
+            PowerUp::SlowBall => "S",
+            PowerUp::StickyPaddle => "G",
+        }
+    }
+}
+
+/// power-ups with a duration the paddle has caught
+#[derive(Component, Default)]
+pub struct ActivePowerUps(Vec<(PowerUp, Timer)>);
+
+impl ActivePowerUps {
+    pub fn has(&self, power_up: PowerUp) -> bool {
+        self.0.iter().any(|(active, _)| *active == power_up)
+    }
+
+    /// starts the power-up or starts it again, wide and narrow paddle cancel each other
+    fn add(&mut self, power_up: PowerUp) {
+        self.0.retain(|(active, _)| {
+            *active != power_up
+                && !matches!(
+                    (*active, power_up),
+                    (PowerUp::WidePaddle, PowerUp::NarrowPaddle)
+                        | (PowerUp::NarrowPaddle, PowerUp::WidePaddle)
+                )
+        });
+        self.0.push((
+            power_up,
+            Timer::from_seconds(POWER_UP_DURATION, TimerMode::Once),
+        ));
+    }
+
+    /// factor for the length of the paddle
+    fn width_factor(&self) -> f32 {
+        if self.has(PowerUp::WidePaddle) {
+            WIDE_PADDLE_FACTOR
+        } else if self.has(PowerUp::NarrowPaddle) {
+            NARROW_PADDLE_FACTOR
+        } else {
+            1.0
+        }
+    }
+}
+
+/// factor for the time of all balls, they are slower if one paddle caught a slow ball
+pub fn ball_time_scale<'a>(paddles: impl IntoIterator<Item = &'a ActivePowerUps>) -> f32 {
+    if paddles
+        .into_iter()
+        .any(|power_ups| power_ups.has(PowerUp::SlowBall))
+    {
+        SLOW_BALL_FACTOR
+    } else {
+        1.0
+    }
+}
+
+/// a ball which is held by a sticky paddle
+#[derive(Component)]
+pub struct Stuck {
+    paddle: Entity,
+    /// position of the ball relative to the paddle
+    offset: Vec2,
+    timer: Timer,
+}
+
+impl Stuck {
+    pub fn new(paddle: Entity, offset: Vec2) -> Self {
+        Self {
+            paddle,
+            offset,
+            timer: Timer::from_seconds(STICKY_HOLD_TIME, TimerMode::Once),
+        }
+    }
+}
+
+/// plugin for the power-ups
+pub struct PowerUpPlugin;
+impl Plugin for PowerUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                // the client of a network game would not see the power-ups
+                drop_power_ups.run_if(not(resource_exists::<NetConnection>)),
+                catch_power_ups,
+                tick_power_ups,
+                apply_power_ups,
+                hold_stuck_balls,
+            )
+                .chain()
+                .after(PongSimulation)
+                .run_if(simulation_running),
+        );
+    }
+}
+
+/// objects drop a power-up by chance when a ball bounces off them
+/// the power-up falls to the paddle which touched the ball last
+fn drop_power_ups(
+    mut commands: Commands,
+    mut object_hits: EventReader<ObjectHit>,
+    paddles: Query<&Paddle>,
+    mut rng: ResMut<PongRng>,
+) {
+    for hit in object_hits.read() {
+        if !rng.random_bool(DROP_CHANCE) {
+            continue;
+        }
+        let power_up = PowerUp::ALL[rng.random_range(0..PowerUp::ALL.len())];
+        let paddle = match hit.paddle {
+            Some(paddle) => paddle,
+            None => {
+                let paddles: Vec<Paddle> = paddles.iter().copied().collect();
+                if paddles.is_empty() {
+                    continue;
+                }
+                paddles[rng.random_range(0..paddles.len())]
+            }
+        };
+
+        commands.spawn((
+            Sprite {
+                color: power_up.color(),
+                custom_size: Some(Vec2::splat(POWER_UP_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(hit.position.extend(0.5)),
+            Position::new(hit.position),
+            Velocity(-paddle.normal() * POWER_UP_SPEED),
+            power_up,
+            OnGameScreen,
+            children![(
+                Text2d::new(power_up.label()),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor(Color::BLACK),
+            )],
+        ));
+    }
+}
+
+/// a power-up which touches a paddle is caught, power-ups outside the game surface are removed
+/// the multi-ball splits a ball at once, the other power-ups last some time
+#[allow(clippy::type_complexity)]
+fn catch_power_ups(
+    mut commands: Commands,
+    power_ups: Query<(Entity, &Position, &PowerUp)>,
+    mut paddles: Query<(&Transform, &Position, &mut ActivePowerUps), With<Paddle>>,
+    balls: Query<
+        (
+            &Position,
+            &Velocity,
+            &Mesh2d,
+            &MeshMaterial2d<ColorMaterial>,
+        ),
+        (With<Ball>, Without<Stuck>),
+    >,
+    all_balls: Query<(), With<Ball>>,
+    surface: Res<GameSurface>,
+) {
+    let mut ball_count = all_balls.iter().count();
+
+    for (entity, position, power_up) in &power_ups {
+        let power_up_box = Aabb2d::new(position.current, Vec2::splat(POWER_UP_SIZE / 2.0));
+        let catcher = paddles.iter_mut().find(|(transform, paddle_position, _)| {
+            bounding_box(transform, Some(*paddle_position)).intersects(&power_up_box)
+        });
+
+        if let Some((_, _, mut active)) = catcher {
+            match power_up {
+                PowerUp::MultiBall => {
+                    let Some((ball_position, velocity, mesh, material)) = balls.iter().next()
+                    else {
+                        commands.entity(entity).despawn();
+                        continue;
+                    };
+                    for angle in [-MULTI_BALL_ANGLE, MULTI_BALL_ANGLE] {
+                        if ball_count >= MAX_BALLS {
+                            break;
+                        }
+                        commands.spawn(ball_bundle(
+                            mesh.0.clone(),
+                            material.0.clone(),
+                            ball_position.current,
+                            Vec2::from_angle(angle).rotate(velocity.0),
+                        ));
+                        ball_count += 1;
+                    }
+                }
+                _ => active.add(*power_up),
+            }
+            commands.entity(entity).despawn();
+        } else if position.current.x < surface.left - POWER_UP_SIZE
+            || position.current.x > surface.right + POWER_UP_SIZE
+            || position.current.y < surface.bottom - POWER_UP_SIZE
+            || position.current.y > surface.top + POWER_UP_SIZE
+        {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// removes the power-ups whose time is over
+fn tick_power_ups(mut paddles: Query<&mut ActivePowerUps>, time: Res<Time>) {
+    for mut active in &mut paddles {
+        active
+            .0
+            .retain_mut(|(_, timer)| !timer.tick(time.delta()).finished());
+    }
+}
+
+/// sets the length and the color of the paddles from their power-ups
+fn apply_power_ups(
+    mut paddles: Query<(&Paddle, &ActivePowerUps, &mut Transform, &mut Sprite)>,
+    level: Res<CurrentLevel>,
+) {
+    for (paddle, active, mut transform, mut sprite) in &mut paddles {
+        let width = level.paddle_width * active.width_factor();
+        match paddle {
+            Paddle::Bottom => transform.scale.x = width,
+            Paddle::Left | Paddle::Right => transform.scale.y = width,
+        }
+
+        let color = if active.has(PowerUp::StickyPaddle) {
+            STICKY_PADDLE_COLOR
+        } else {
+            PADDLE_COLOR
+        };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+/// held balls move with their paddle and are launched after the hold time
+/// the launch direction depends on where the ball sits and how the paddle moves
+fn hold_stuck_balls(
+    mut commands: Commands,
+    mut balls: Query<(Entity, &mut Position, &mut Velocity, &mut Stuck), With<Ball>>,
+    paddles: Query<(&Transform, &Position, &Paddle, &PaddleIntent), Without<Ball>>,
+    time: Res<Time>,
+) {
+    for (ball, mut position, mut velocity, mut stuck) in &mut balls {
+        let Ok((transform, paddle_position, paddle, intent)) = paddles.get(stuck.paddle) else {
+            commands.entity(ball).remove::<Stuck>();
+            continue;
+        };
+
+        position.current = paddle_position.current + stuck.offset;
+        if stuck.timer.tick(time.delta()).finished() {
+            let paddle_box = bounding_box(transform, Some(paddle_position));
+            velocity.0 =
+                paddle_bounce(*paddle, position.current, paddle_box, intent.0) * velocity.length();
+            commands.entity(ball).remove::<Stuck>();
+        }
+    }
+}
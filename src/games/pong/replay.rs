@@ -0,0 +1,487 @@
+/// recording and playback of pong matches
+/// the simulation is deterministic, so the seed, the levels and the inputs
+/// of the human paddles are enough to play a match again
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{app::FixedMain, prelude::*};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::enums::GameState;
+use crate::functions::data_dir;
+use crate::games::pong::{
+    ai::{CpuPlayer, PaddleControllers},
+    game::{OnGameScreen, Paddle, PaddleIntent, PongMode, PongState},
+    level::PongLevel,
+    rules::MatchRules,
+};
+
+// replay constanten
+/// folder of the replay files in the data directory
+const REPLAY_DIR: &str = "replays";
+const REPLAY_EXTENSION: &str = "replay.ron";
+/// replay files which are kept, the oldest are deleted when a new one is saved
+pub const MAX_REPLAYS: usize = 20;
+/// version of the file format, older files can not be played
+const REPLAY_VERSION: u32 = 1;
+/// intents are saved as whole numbers from -INTENT_STEPS to INTENT_STEPS
+pub const INTENT_STEPS: f32 = 127.0;
+/// playback speeds the fast-forward cycles through
+const PLAYBACK_SPEEDS: [f32; 4] = [1.0, 2.0, 4.0, 8.0];
+
+/// everything needed to play a match again
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Replay {
+    version: u32,
+    pub seed: u64,
+    pub mode: PongMode,
+    pub controllers: PaddleControllers,
+    pub rules: MatchRules,
+    /// one segment for every time the level was loaded
+    pub segments: Vec<ReplaySegment>,
+}
+
+/// the simulation steps between two level loads
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ReplaySegment {
+    pub level: PongLevel,
+    /// intents of the human paddles in paddle order,
+    /// steps with the same intents are saved once with their count
+    inputs: Vec<(u32, Vec<i8>)>,
+}
+
+impl ReplaySegment {
+    fn new(level: &PongLevel) -> Self {
+        Self {
+            level: level.clone(),
+            inputs: Vec::new(),
+        }
+    }
+
+    /// adds the intents of one simulation step
+    fn push(&mut self, intents: Vec<i8>) {
+        match self.inputs.last_mut() {
+            Some((count, last)) if *last == intents => *count += 1,
+            _ => self.inputs.push((1, intents)),
+        }
+    }
+}
+
+/// errors while saving or loading a replay file
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Could not access replay: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Could not parse replay: {0}")]
+    Parse(#[from] ron::error::SpannedError),
+    #[error("Could not write replay: {0}")]
+    Write(#[from] ron::Error),
+    #[error("Replay version {0} is not supported")]
+    Version(u32),
+}
+
+/// records the running match, exists in every game that is not a replay
+#[derive(Resource)]
+pub struct ReplayRecorder {
+    seed: u64,
+    segments: Vec<ReplaySegment>,
+}
+
+impl ReplayRecorder {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            segments: Vec::new(),
+        }
+    }
+
+    /// starts a new segment after the level was loaded
+    pub fn start_segment(&mut self, level: &PongLevel) {
+        self.segments.push(ReplaySegment::new(level));
+    }
+
+    /// the replay of the match recorded so far
+    pub fn replay(
+        &self,
+        mode: PongMode,
+        controllers: &PaddleControllers,
+        rules: &MatchRules,
+    ) -> Replay {
+        Replay {
+            version: REPLAY_VERSION,
+            seed: self.seed,
+            mode,
+            controllers: controllers.clone(),
+            rules: rules.clone(),
+            segments: self.segments.clone(),
+        }
+    }
+}
+
+/// plays a replay, while it exists the keyboard does not move the paddles
+/// the rules of the replay replace the rules of the player until the playback ends
+#[derive(Resource)]
+pub struct ReplayPlayer {
+    replay: Replay,
+    /// rules of the player, they are set again when the playback ends
+    player_rules: MatchRules,
+    /// segment which is played, None before the first level is loaded
+    segment: Option<usize>,
+    /// position in the inputs of the segment
+    run: usize,
+    run_step: u32,
+    /// index into PLAYBACK_SPEEDS
+    speed: usize,
+    /// simulation steps to run while the playback is paused
+    steps: u32,
+}
+
+impl ReplayPlayer {
+    pub fn new(replay: Replay, player_rules: MatchRules) -> Self {
+        Self {
+            replay,
+            player_rules,
+            segment: None,
+            run: 0,
+            run_step: 0,
+            speed: 0,
+            steps: 0,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    /// plays the replay from the start again
+    pub fn rewind(&mut self) {
+        self.segment = None;
+        self.run = 0;
+        self.run_step = 0;
+    }
+
+    /// moves to the next segment after the level was loaded
+    /// None if the recording has no more segments
+    pub fn start_segment(&mut self) -> Option<&ReplaySegment> {
+        let next = self.segment.map_or(0, |segment| segment + 1);
+        self.segment = Some(next);
+        self.run = 0;
+        self.run_step = 0;
+        self.replay.segments.get(next)
+    }
+
+    /// true if all steps of the segment are played
+    pub fn segment_finished(&self) -> bool {
+        self.segment
+            .and_then(|segment| self.replay.segments.get(segment))
+            .is_none_or(|segment| self.run >= segment.inputs.len())
+    }
+
+    /// true if there is a segment after the played one
+    fn has_next_segment(&self) -> bool {
+        self.segment
+            .is_some_and(|segment| segment + 1 < self.replay.segments.len())
+    }
+
+    /// intents of the next simulation step
+    fn next_inputs(&mut self) -> Option<&[i8]> {
+        let segment = self.replay.segments.get(self.segment?)?;
+        let (count, intents) = segment.inputs.get(self.run)?;
+
+        self.run_step += 1;
+        if self.run_step >= *count {
+            self.run += 1;
+            self.run_step = 0;
+        }
+        Some(intents)
+    }
+}
+
+/// plugin for recording and playing replays
+pub struct ReplayPlugin;
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(PongState::Loading),
+            replay_overlay_setup.run_if(resource_exists::<ReplayPlayer>),
+        )
+        .add_systems(
+            OnEnter(PongState::GameOver),
+            save_replay.run_if(resource_exists::<ReplayRecorder>),
+        )
+        // a match which is quit from the pause menu is saved as far as it was played
+        .add_systems(
+            OnExit(GameState::Pong),
+            (
+                save_replay.run_if(resource_exists::<ReplayRecorder>),
+                stop_replay,
+            )
+                .chain(),
+        )
+        .add_systems(
+            Update,
+            (
+                replay_controls,
+                (step_replay.run_if(replay_step_pending), next_segment)
+                    .run_if(in_state(PongState::Game)),
+                update_replay_overlay,
+            )
+                .chain()
+                .run_if(in_state(PongState::Loading).or(in_state(PongState::Game)))
+                .run_if(resource_exists::<ReplayPlayer>),
+        );
+    }
+}
+
+/// the text with the playback state
+#[derive(Component)]
+struct ReplayOverlayText;
+
+/// spawns the playback state and the keys at the bottom of the screen
+fn replay_overlay_setup(
+    mut commands: Commands,
+    player: Res<ReplayPlayer>,
+    time: Res<Time<Virtual>>,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        OnGameScreen,
+        children![
+            (
+                Text::new(replay_overlay_text(&player, &time)),
+                TextFont {
+                    font_size: 30.0,
+                    ..default()
+                },
+                ReplayOverlayText,
+            ),
+            (
+                Text::new("Space: pause   F: fast-forward   N: next step"),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+            )
+        ],
+    ));
+}
+
+/// text for the playback state
+fn replay_overlay_text(player: &ReplayPlayer, time: &Time<Virtual>) -> String {
+    if player.segment_finished() && !player.has_next_segment() {
+        "Replay finished".to_string()
+    } else if time.is_paused() {
+        "Replay paused".to_string()
+    } else {
+        format!("Replay x{}", PLAYBACK_SPEEDS[player.speed])
+    }
+}
+
+/// writes the playback state into the overlay
+fn update_replay_overlay(
+    player: Res<ReplayPlayer>,
+    time: Res<Time<Virtual>>,
+    mut text: Single<&mut Text, With<ReplayOverlayText>>,
+) {
+    text.0 = replay_overlay_text(&player, &time);
+}
+
+/// pause, fast-forward and stepping of the playback
+/// pausing the virtual time stops the fixed timestep of the simulation
+fn replay_controls(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut player: ResMut<ReplayPlayer>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        if time.is_paused() {
+            time.unpause();
+        } else {
+            time.pause();
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyF) {
+        player.speed = (player.speed + 1) % PLAYBACK_SPEEDS.len();
+        time.set_relative_speed(PLAYBACK_SPEEDS[player.speed]);
+    }
+    if keyboard_input.just_pressed(KeyCode::KeyN) && time.is_paused() {
+        player.steps += 1;
+    }
+}
+
+/// true if a step was requested while the playback is paused
+fn replay_step_pending(player: Res<ReplayPlayer>) -> bool {
+    player.steps > 0
+}
+
+/// runs the requested simulation steps while the virtual time is paused
+fn step_replay(world: &mut World) {
+    let steps = std::mem::take(&mut world.resource_mut::<ReplayPlayer>().steps);
+    for _ in 0..steps {
+        *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+        world.run_schedule(FixedMain);
+    }
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+}
+
+/// loads the next level when the steps of the segment are played,
+/// the recorded match loaded it at the same step
+fn next_segment(player: Res<ReplayPlayer>, mut pong_state: ResMut<NextState<PongState>>) {
+    if player.segment_finished() && player.has_next_segment() {
+        pong_state.set(PongState::Loading);
+    }
+}
+
+/// intents of the human paddles sorted by paddle
+fn human_intents<'a>(
+    paddles: &'a mut Query<(&Paddle, &mut PaddleIntent), Without<CpuPlayer>>,
+) -> Vec<(Paddle, Mut<'a, PaddleIntent>)> {
+    let mut intents: Vec<_> = paddles
+        .iter_mut()
+        .map(|(paddle, intent)| (*paddle, intent))
+        .collect();
+    intents.sort_by_key(|(paddle, _)| *paddle);
+    intents
+}
+
+/// saves the intents of the human paddles for this simulation step
+/// the intents are rounded first, so the recorded match moves like its replay
+pub fn record_intent(
+    mut recorder: ResMut<ReplayRecorder>,
+    mut paddles: Query<(&Paddle, &mut PaddleIntent), Without<CpuPlayer>>,
+) {
+    let Some(segment) = recorder.segments.last_mut() else {
+        return;
+    };
+
+    let mut inputs = Vec::new();
+    for (_, mut intent) in human_intents(&mut paddles) {
+        let steps = (intent.0.clamp(-1.0, 1.0) * INTENT_STEPS).round() as i8;
+        intent.0 = steps as f32 / INTENT_STEPS;
+        inputs.push(steps);
+    }
+    segment.push(inputs);
+}
+
+/// sets the intents of the human paddles from the replay
+pub fn replay_intent(
+    mut player: ResMut<ReplayPlayer>,
+    mut paddles: Query<(&Paddle, &mut PaddleIntent), Without<CpuPlayer>>,
+) {
+    let Some(inputs) = player.next_inputs() else {
+        return;
+    };
+
+    for ((_, mut intent), steps) in human_intents(&mut paddles).into_iter().zip(inputs) {
+        intent.0 = *steps as f32 / INTENT_STEPS;
+    }
+}
+
+/// writes the recorded match into the replay folder
+fn save_replay(
+    mut commands: Commands,
+    recorder: Res<ReplayRecorder>,
+    mode: Res<PongMode>,
+    controllers: Res<PaddleControllers>,
+    rules: Res<MatchRules>,
+) {
+    let replay = recorder.replay(*mode, &controllers, &rules);
+    if let Err(e) = write_replay(&replay_dir(), &replay) {
+        eprintln!("{}", e);
+    }
+    commands.remove_resource::<ReplayRecorder>();
+}
+
+/// writes the replay into a new file named after the mode and the time in milliseconds
+/// a time which has a file already is counted up, then the oldest files over MAX_REPLAYS are deleted
+pub fn write_replay(dir: &Path, replay: &Replay) -> Result<PathBuf, ReplayError> {
+    let mut millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_millis());
+    let path = loop {
+        let name = format!("{:?}_{}.{}", replay.mode, millis, REPLAY_EXTENSION).to_lowercase();
+        let path = dir.join(name);
+        if !path.exists() {
+            break path;
+        }
+        millis += 1;
+    };
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, ron::to_string(replay)?)?;
+    for old in replay_files(dir).iter().skip(MAX_REPLAYS) {
+        fs::remove_file(old)?;
+    }
+    Ok(path)
+}
+
+/// folder of the replay files
+fn replay_dir() -> PathBuf {
+    data_dir().join(REPLAY_DIR)
+}
+
+/// reads a replay file
+pub fn load_replay(path: &Path) -> Result<Replay, ReplayError> {
+    let replay: Replay = ron::de::from_bytes(&fs::read(path)?)?;
+    if replay.version != REPLAY_VERSION {
+        return Err(ReplayError::Version(replay.version));
+    }
+    Ok(replay)
+}
+
+/// all replay files, the newest first
+pub fn list_replays() -> Vec<PathBuf> {
+    replay_files(&replay_dir())
+}
+
+/// the replay files in the folder, the newest first
+/// files with the same time are sorted by their name, which has the time in it
+pub fn replay_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut replays: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.ends_with(REPLAY_EXTENSION))
+        })
+        .collect();
+    replays.sort_by_key(|path| {
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        (modified, path.clone())
+    });
+    replays.reverse();
+    replays
+}
+
+/// ends the playback or recording when the pong game is left
+/// after a playback the player gets the own rules back, so the rules screen does not save the replay rules
+fn stop_replay(
+    mut commands: Commands,
+    player: Option<Res<ReplayPlayer>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if let Some(player) = player {
+        commands.insert_resource(player.player_rules.clone());
+    }
+    commands.remove_resource::<ReplayPlayer>();
+    commands.remove_resource::<ReplayRecorder>();
+    time.unpause();
+    time.set_relative_speed(1.0);
+}
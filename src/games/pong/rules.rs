@@ -0,0 +1,165 @@
+/// the rules of a match, chosen on the rules screen before the game starts
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::functions::{ConfigError, load_config, save_config};
+use crate::games::pong::{game::Wall, level::PongLevel, score::LIVES, versus::POINTS_TO_WIN};
+
+// rules constanten
+const RULES_FILE: &str = "rules.ron";
+const MAX_LIVES: u32 = 9;
+const MAX_POINTS_TO_WIN: u32 = 21;
+/// smallest and biggest factor for the ball speed and the paddle size
+const MIN_FACTOR: f32 = 0.5;
+const MAX_FACTOR: f32 = 2.0;
+const FACTOR_STEP: f32 = 0.25;
+
+/// the rules a player can change
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Rule {
+    Lives,
+    PointsToWin,
+    BallSpeed,
+    PaddleSize,
+    TopWall,
+}
+
+impl Rule {
+    pub const ALL: [Rule; 5] = [
+        Rule::Lives,
+        Rule::PointsToWin,
+        Rule::BallSpeed,
+        Rule::PaddleSize,
+        Rule::TopWall,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rule::Lives => "Lives",
+            Rule::PointsToWin => "Points to win",
+            Rule::BallSpeed => "Ball speed",
+            Rule::PaddleSize => "Paddle size",
+            Rule::TopWall => "Top wall",
+        }
+    }
+}
+
+/// the rules of the match, saved in the config directory
+/// speed and size are factors for the values of the level,
+/// the speed of the paddles and the size of the ball stay the same in every match
+#[derive(Resource, Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct MatchRules {
+    pub lives: u32,
+    pub points_to_win: u32,
+    pub ball_speed: f32,
+    pub paddle_size: f32,
+    /// without a solid top wall a ball is lost through the top
+    pub solid_top_wall: bool,
+}
+
+impl Default for MatchRules {
+    fn default() -> Self {
+        Self {
+            lives: LIVES,
+            points_to_win: POINTS_TO_WIN,
+            ball_speed: 1.0,
+            paddle_size: 1.0,
+            solid_top_wall: true,
+        }
+    }
+}
+
+impl MatchRules {
+    /// changes the rule one step up or down, the top wall switches in both directions
+    pub fn change(&mut self, rule: Rule, up: bool) {
+        let factor_step = if up { FACTOR_STEP } else { -FACTOR_STEP };
+        match rule {
+            Rule::Lives => self.lives = step(self.lives, up).clamp(1, MAX_LIVES),
+            Rule::PointsToWin => {
+                self.points_to_win = step(self.points_to_win, up).clamp(1, MAX_POINTS_TO_WIN)
+            }
+            Rule::BallSpeed => {
+                self.ball_speed = (self.ball_speed + factor_step).clamp(MIN_FACTOR, MAX_FACTOR)
+            }
+            Rule::PaddleSize => {
+                self.paddle_size = (self.paddle_size + factor_step).clamp(MIN_FACTOR, MAX_FACTOR)
+            }
+            Rule::TopWall => self.solid_top_wall = !self.solid_top_wall,
+        }
+    }
+
+    /// keeps the rules in the limits of the rules screen, for rules from a changed file
+    /// factors which are no number are set back to the default
+    pub fn clamp(&mut self) {
+        let factor = |value: f32| {
+            if value.is_nan() {
+                1.0
+            } else {
+                value.clamp(MIN_FACTOR, MAX_FACTOR)
+            }
+        };
+        self.lives = self.lives.clamp(1, MAX_LIVES);
+        self.points_to_win = self.points_to_win.clamp(1, MAX_POINTS_TO_WIN);
+        self.ball_speed = factor(self.ball_speed);
+        self.paddle_size = factor(self.paddle_size);
+    }
+
+    /// the value of the rule for the rules screen
+    pub fn value_text(&self, rule: Rule) -> String {
+        match rule {
+            Rule::Lives => self.lives.to_string(),
+            Rule::PointsToWin => self.points_to_win.to_string(),
+            Rule::BallSpeed => format!("{:.0}%", self.ball_speed * 100.0),
+            Rule::PaddleSize => format!("{:.0}%", self.paddle_size * 100.0),
+            Rule::TopWall => {
+                if self.solid_top_wall {
+                    "Solid".to_string()
+                } else {
+                    "Open".to_string()
+                }
+            }
+        }
+    }
+
+    /// changes the speed, the paddles and the walls of the level
+    pub fn apply(&self, level: &mut PongLevel) {
+        level.ball_speed *= self.ball_speed;
+        level.paddle_width *= self.paddle_size;
+        if !self.solid_top_wall && !level.exit_walls.contains(&Wall::Top) {
+            level.exit_walls.push(Wall::Top);
+        }
+    }
+}
+
+fn step(value: u32, up: bool) -> u32 {
+    if up {
+        value.saturating_add(1)
+    } else {
+        value.saturating_sub(1)
+    }
+}
+
+/// plugin which loads the saved rules
+pub struct RulesPlugin;
+impl Plugin for RulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_match_rules);
+    }
+}
+
+/// reads the saved rules, without a file the default rules are used
+fn load_match_rules(mut commands: Commands) {
+    match load_config::<MatchRules>(RULES_FILE) {
+        Ok(Some(mut rules)) => {
+            rules.clamp();
+            commands.insert_resource(rules);
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+}
+
+/// writes the rules into the config directory
+pub fn save_match_rules(rules: &MatchRules) -> Result<(), ConfigError> {
+    save_config(RULES_FILE, rules)
+}
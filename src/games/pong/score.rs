@@ -0,0 +1,115 @@
+/// score, lives and play time of the pong game
+use bevy::prelude::*;
+
+use crate::games::pong::game::{
+    BallLost, OnGameScreen, PongMode, PongSimulation, PongState, simulation_running,
+};
+
+// score constanten
+pub const LIVES: u32 = 3;
+pub const POINTS_PER_HIT: u32 = 1;
+
+/// points, lives and survived time of the running game
+#[derive(Resource)]
+pub struct Score {
+    pub points: u32,
+    pub lives: u32,
+    pub time: f32,
+}
+
+impl Default for Score {
+    fn default() -> Self {
+        Self::with_lives(LIVES)
+    }
+}
+
+impl Score {
+    pub fn with_lives(lives: u32) -> Self {
+        Self {
+            points: 0,
+            lives,
+            time: 0.0,
+        }
+    }
+}
+
+/// the text that shows the score while playing
+#[derive(Component)]
+struct ScoreText;
+
+/// plugin for the score display
+pub struct ScorePlugin;
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            OnEnter(PongState::Loading),
+            score_setup.run_if(not(resource_equals(PongMode::Versus))),
+        )
+        .add_systems(
+            FixedUpdate,
+            survival_time
+                .after(PongSimulation)
+                .run_if(simulation_running),
+        )
+        .add_systems(
+            FixedUpdate,
+            lose_life
+                .after(survival_time)
+                .run_if(simulation_running)
+                .run_if(not(resource_equals(PongMode::Versus))),
+        )
+        .add_systems(
+            Update,
+            update_score_text
+                .run_if(in_state(PongState::Game))
+                .run_if(not(resource_equals(PongMode::Versus))),
+        );
+    }
+}
+
+/// spawns the score text
+fn score_setup(mut commands: Commands, score: Res<Score>) {
+    commands.spawn((
+        Text::new(score_text(&score)),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            ..default()
+        },
+        ScoreText,
+        OnGameScreen,
+    ));
+}
+
+/// counts the time the player survived
+fn survival_time(mut score: ResMut<Score>, time: Res<Time>) {
+    score.time += time.delta_secs();
+}
+
+/// every lost ball costs a life
+/// without lives left change PongState to PongState::GameOver
+fn lose_life(
+    mut ball_lost: EventReader<BallLost>,
+    mut score: ResMut<Score>,
+    mut pong_state: ResMut<NextState<PongState>>,
+) {
+    for _ in ball_lost.read() {
+        score.lives = score.lives.saturating_sub(1);
+        if score.lives == 0 {
+            pong_state.set(PongState::GameOver);
+        }
+    }
+}
+
+/// writes the current score into the score text
+fn update_score_text(score: Res<Score>, mut text: Single<&mut Text, With<ScoreText>>) {
+    if score.is_changed() {
+        text.0 = score_text(&score);
+    }
+}
+
+/// text for the score display
+fn score_text(score: &Score) -> String {
+    format!("Score: {}   Lives: {}", score.points, score.lives)
+}
@@ -0,0 +1,205 @@
+/// sound effects and music of the pong game
+/// all sounds are generated tones, so the game needs no audio files
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::random_range;
+
+use crate::audio::AudioSettings;
+use crate::games::pong::{
+    bricks::BrickHit,
+    game::{BallLost, ObjectHit, PaddleHit, PongMode, PongState},
+};
+
+// sound constanten
+/// the pitch of every sound effect varies by this factor
+const PITCH_VARIATION: f32 = 0.1;
+const PADDLE_SOUND: (f32, f32) = (440.0, 0.06);
+const WALL_SOUND: (f32, f32) = (220.0, 0.05);
+const BRICK_SOUND: (f32, f32) = (660.0, 0.08);
+const LOST_SOUND: (f32, f32) = (110.0, 0.4);
+
+// music constanten
+/// the melodies as semitones from a4, none is a rest
+const CLASSIC_MELODY: [Option<i32>; 8] = [
+    Some(-9),
+    Some(-5),
+    Some(-2),
+    Some(-5),
+    Some(-7),
+    Some(-4),
+    Some(0),
+    None,
+];
+const BREAKOUT_MELODY: [Option<i32>; 8] = [
+    Some(-12),
+    Some(-9),
+    Some(-5),
+    Some(-9),
+    Some(-10),
+    Some(-7),
+    Some(-3),
+    None,
+];
+const VERSUS_MELODY: [Option<i32>; 8] = [
+    Some(-12),
+    None,
+    Some(-12),
+    Some(-5),
+    Some(-7),
+    None,
+    Some(-7),
+    Some(-2),
+];
+
+/// the tones of the sound effects
+#[derive(Resource)]
+struct SoundEffects {
+    paddle: Handle<Pitch>,
+    wall: Handle<Pitch>,
+    brick: Handle<Pitch>,
+    lost: Handle<Pitch>,
+}
+
+/// the notes of the music of the current mode, played one after another
+#[derive(Resource)]
+struct Music {
+    notes: Vec<Option<Handle<Pitch>>>,
+    next: usize,
+    timer: Timer,
+}
+
+/// plugin for the sounds of the pong game
+pub struct SoundPlugin;
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, sound_setup)
+            .add_systems(OnEnter(PongState::Loading), music_setup)
+            .add_systems(OnEnter(PongState::Disabled), stop_music)
+            .add_systems(
+                Update,
+                (paddle_sound, wall_sound, lost_sound).run_if(in_state(PongState::Game)),
+            )
+            .add_systems(
+                Update,
+                play_music
+                    .run_if(resource_exists::<Music>)
+                    .run_if(in_state(PongState::Loading).or(in_state(PongState::Game))),
+            );
+    }
+}
+
+/// frequency of the note the semitones away from a4
+fn note_frequency(semitones: i32) -> f32 {
+    440.0 * 2.0_f32.powf(semitones as f32 / 12.0)
+}
+
+/// a tone with the frequency and the length in seconds
+fn tone(pitches: &mut Assets<Pitch>, (frequency, length): (f32, f32)) -> Handle<Pitch> {
+    pitches.add(Pitch::new(frequency, Duration::from_secs_f32(length)))
+}
+
+fn sound_setup(mut commands: Commands, mut pitches: ResMut<Assets<Pitch>>) {
+    commands.insert_resource(SoundEffects {
+        paddle: tone(&mut pitches, PADDLE_SOUND),
+        wall: tone(&mut pitches, WALL_SOUND),
+        brick: tone(&mut pitches, BRICK_SOUND),
+        lost: tone(&mut pitches, LOST_SOUND),
+    });
+}
+
+/// plays the sound effect with a slightly random pitch
+/// the sounds do not need the seeded rng of the game
+fn play_effect(commands: &mut Commands, settings: &AudioSettings, sound: &Handle<Pitch>) {
+    let speed = random_range(1.0 - PITCH_VARIATION..1.0 + PITCH_VARIATION);
+    commands.spawn((AudioPlayer(sound.clone()), settings.effect_playback(speed)));
+}
+
+fn paddle_sound(
+    mut commands: Commands,
+    mut paddle_hits: EventReader<PaddleHit>,
+    sounds: Res<SoundEffects>,
+    settings: Res<AudioSettings>,
+) {
+    for _ in paddle_hits.read() {
+        play_effect(&mut commands, &settings, &sounds.paddle);
+    }
+}
+
+/// every brick hit is also an object hit, the other object hits are walls
+fn wall_sound(
+    mut commands: Commands,
+    mut object_hits: EventReader<ObjectHit>,
+    mut brick_hits: EventReader<BrickHit>,
+    sounds: Res<SoundEffects>,
+    settings: Res<AudioSettings>,
+) {
+    let bricks = brick_hits.read().count();
+    let walls = object_hits.read().count().saturating_sub(bricks);
+    for _ in 0..bricks {
+        play_effect(&mut commands, &settings, &sounds.brick);
+    }
+    for _ in 0..walls {
+        play_effect(&mut commands, &settings, &sounds.wall);
+    }
+}
+
+fn lost_sound(
+    mut commands: Commands,
+    mut ball_lost: EventReader<BallLost>,
+    sounds: Res<SoundEffects>,
+    settings: Res<AudioSettings>,
+) {
+    for _ in ball_lost.read() {
+        play_effect(&mut commands, &settings, &sounds.lost);
+    }
+}
+
+/// every mode has its own melody and tempo
+fn music_setup(
+    mut commands: Commands,
+    mut pitches: ResMut<Assets<Pitch>>,
+    mode: Res<PongMode>,
+    music: Option<Res<Music>>,
+) {
+    // the music goes on when the next round of the same game starts
+    if music.is_some() {
+        return;
+    }
+
+    let (melody, beat) = match *mode {
+        PongMode::Classic => (CLASSIC_MELODY, 0.3),
+        PongMode::Breakout => (BREAKOUT_MELODY, 0.25),
+        PongMode::Versus => (VERSUS_MELODY, 0.2),
+    };
+    let notes = melody
+        .iter()
+        .map(|note| note.map(|semitones| tone(&mut pitches, (note_frequency(semitones), beat))))
+        .collect();
+    commands.insert_resource(Music {
+        notes,
+        next: 0,
+        timer: Timer::from_seconds(beat, TimerMode::Repeating),
+    });
+}
+
+/// starts the next note on every beat
+fn play_music(
+    mut commands: Commands,
+    mut music: ResMut<Music>,
+    settings: Res<AudioSettings>,
+    time: Res<Time<Real>>,
+) {
+    for _ in 0..music.timer.tick(time.delta()).times_finished_this_tick() {
+        let index = music.next;
+        music.next = (index + 1) % music.notes.len();
+        if let Some(note) = &music.notes[index] {
+            commands.spawn((AudioPlayer(note.clone()), settings.music_playback()));
+        }
+    }
+}
+
+/// the music ends with the game
+fn stop_music(mut commands: Commands) {
+    commands.remove_resource::<Music>();
+}
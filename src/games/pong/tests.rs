@@ -0,0 +1,687 @@
+/// tests of the pong simulation in the headless harness
+use std::{
+    fs,
+    net::{Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+use std::{thread, time::Duration};
+
+use bevy::{math::bounding::Aabb2d, prelude::*};
+
+use crate::games::pong::{
+    ai::{CpuSide, Difficulty, PaddleControllers, predict_ball},
+    game::{
+        BALL_DIAMETER, BALL_SPEED, BALL_SPEED_UP, MAX_BALL_SPEED_FACTOR, MAX_BOUNCE_ANGLE, Paddle,
+        PongMode, PongState, SIMULATION_HZ, Wall, paddle_bounce,
+    },
+    headless::{HeadlessPong, SEED},
+    level::PongLevel,
+    network::NetConnection,
+    power_ups::{
+        POWER_UP_DURATION, PowerUp, SLOW_BALL_FACTOR, STICKY_HOLD_TIME, WIDE_PADDLE_FACTOR,
+    },
+    replay::{MAX_REPLAYS, ReplayPlayer, replay_files, write_replay},
+    rules::MatchRules,
+    score::LIVES,
+    versus::POINTS_TO_WIN,
+};
+
+/// a small arena, the walls are at x = ±200 and y = ±150
+const ARENA: Vec2 = Vec2::new(400.0, 300.0);
+/// seconds of one simulation step
+const STEP: f32 = (1.0 / SIMULATION_HZ) as f32;
+
+fn classic_game() -> HeadlessPong {
+    HeadlessPong::new(PongMode::Classic, PongLevel::default(), ARENA).start()
+}
+
+fn versus_level() -> PongLevel {
+    PongLevel {
+        exit_walls: vec![Wall::Left, Wall::Right],
+        ..default()
+    }
+}
+
+fn versus_game() -> HeadlessPong {
+    HeadlessPong::new(PongMode::Versus, versus_level(), ARENA).start()
+}
+
+/// a versus game of the connection, the paddles are moved like in its network game
+fn network_game(connection: NetConnection) -> HeadlessPong {
+    HeadlessPong::new(PongMode::Versus, versus_level(), ARENA)
+        .with_resource(connection.controllers())
+        .with_resource(connection)
+}
+
+/// runs the game until it has played exactly the given number of steps
+fn step_to(game: &mut HeadlessPong, steps: u32) {
+    assert!(game.step_until(steps, |game| game.steps() >= steps));
+    assert_eq!(game.steps(), steps);
+}
+
+/// the bits of the vector, so positions are compared without tolerance
+fn bits(vector: Vec2) -> [u32; 2] {
+    vector.to_array().map(f32::to_bits)
+}
+
+/// updates the game until the messages of the other side have arrived
+fn update_until(game: &mut HeadlessPong, condition: impl Fn(&HeadlessPong) -> bool) -> bool {
+    for _ in 0..100 {
+        game.update();
+        if condition(game) {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    false
+}
+
+/// the client steps first, so the host has its input for the same step
+fn step_both(host: &mut HeadlessPong, client: &mut HeadlessPong, steps: u32) {
+    for _ in 0..steps {
+        client.step(1);
+        host.step(1);
+    }
+}
+
+#[test]
+fn ball_moves_with_its_velocity() {
+    let mut game = classic_game();
+    let (start, velocity) = game.ball();
+    let steps = game.steps();
+
+    game.step(10);
+
+    assert_eq!(game.steps(), steps + 10);
+    let (position, _) = game.ball();
+    let expected = start + velocity * STEP * 10.0;
+    assert!(
+        position.distance(expected) < 0.01,
+        "ball at {position}, expected {expected}"
+    );
+}
+
+#[test]
+fn ball_bounces_off_the_top_wall() {
+    let mut game = classic_game();
+    game.set_ball(Vec2::new(0.0, 100.0), Vec2::new(0.0, 500.0));
+
+    game.step(20);
+
+    let (position, velocity) = game.ball();
+    assert!(velocity.y < 0.0, "ball still moves up with {velocity}");
+    assert!(position.y < ARENA.y / 2.0 - BALL_DIAMETER / 2.0);
+    assert_eq!(game.events().object_hits, 1);
+}
+
+#[test]
+fn keys_move_the_paddle() {
+    let mut game = classic_game();
+    let start = game.paddle(Paddle::Bottom);
+
+    game.press(KeyCode::ArrowRight);
+    game.step(10);
+    game.release(KeyCode::ArrowRight);
+    let moved = game.paddle(Paddle::Bottom);
+    game.step(10);
+
+    assert!(moved.x > start.x, "paddle did not move right");
+    assert_eq!(game.paddle(Paddle::Bottom), moved);
+}
+
+#[test]
+fn simulation_does_not_depend_on_the_framerate() {
+    // one step per frame, two frames per step and three steps per frame
+    let frames = [1.0, 0.5, 3.0];
+    let mut games: Vec<HeadlessPong> = frames
+        .iter()
+        .map(|steps_per_frame| {
+            let mut game = HeadlessPong::new(PongMode::Classic, PongLevel::default(), ARENA)
+                .with_frame_time(Duration::from_secs_f64(steps_per_frame / SIMULATION_HZ));
+            game.press(KeyCode::ArrowRight);
+            game.start()
+        })
+        .collect();
+
+    for game in &mut games {
+        step_to(game, 45);
+        game.release(KeyCode::ArrowRight);
+        game.press(KeyCode::ArrowLeft);
+        step_to(game, 240);
+    }
+
+    let (first, others) = games.split_first_mut().unwrap();
+    let (ball, velocity) = first.ball();
+    let paddle = first.paddle(Paddle::Bottom);
+    for game in others {
+        let (other_ball, other_velocity) = game.ball();
+        assert_eq!(bits(other_ball), bits(ball));
+        assert_eq!(bits(other_velocity), bits(velocity));
+        assert_eq!(bits(game.paddle(Paddle::Bottom)), bits(paddle));
+        assert_eq!(game.events().object_hits, first.events().object_hits);
+    }
+}
+
+#[test]
+fn replay_plays_the_recorded_match() {
+    let mut game = classic_game();
+    // the paddle follows the ball, so the replay has to play the same hits
+    for _ in 0..40 {
+        let (ball, _) = game.ball();
+        let paddle = game.paddle(Paddle::Bottom);
+        game.release(KeyCode::ArrowLeft);
+        game.release(KeyCode::ArrowRight);
+        if ball.x > paddle.x + 10.0 {
+            game.press(KeyCode::ArrowRight);
+        } else if ball.x < paddle.x - 10.0 {
+            game.press(KeyCode::ArrowLeft);
+        }
+        game.step(10);
+    }
+    assert!(game.events().paddle_hits > 0);
+
+    let mut replayed = HeadlessPong::new(PongMode::Classic, PongLevel::default(), ARENA)
+        .with_resource(ReplayPlayer::new(game.replay(), MatchRules::default()))
+        .start();
+    step_to(&mut replayed, game.steps());
+
+    let (ball, velocity) = game.ball();
+    let (replayed_ball, replayed_velocity) = replayed.ball();
+    assert_eq!(bits(replayed_ball), bits(ball));
+    assert_eq!(bits(replayed_velocity), bits(velocity));
+    assert_eq!(
+        bits(replayed.paddle(Paddle::Bottom)),
+        bits(game.paddle(Paddle::Bottom))
+    );
+    assert_eq!(replayed.score().points, game.score().points);
+    assert_eq!(replayed.score().lives, game.score().lives);
+    assert_eq!(replayed.events().paddle_hits, game.events().paddle_hits);
+}
+
+#[test]
+fn replay_keeps_the_seed_of_the_recording() {
+    let record = |seed| {
+        HeadlessPong::new(PongMode::Versus, versus_level(), ARENA)
+            .with_resource(PaddleControllers::against_cpu(
+                CpuSide::Right,
+                Difficulty::Easy,
+            ))
+            .with_seed(seed)
+            .start()
+    };
+    // the easy cpu misses by chance, so it moves with the seed of the game
+    let mut game = record(7);
+    game.step(300);
+    let mut other = record(SEED);
+    other.step(300);
+    assert_ne!(
+        bits(other.paddle(Paddle::Right)),
+        bits(game.paddle(Paddle::Right))
+    );
+
+    let replay = game.replay();
+    assert_eq!(replay.seed, 7);
+    let mut replayed = HeadlessPong::new(PongMode::Versus, versus_level(), ARENA)
+        .with_resource(replay.controllers.clone())
+        .with_resource(ReplayPlayer::new(replay, MatchRules::default()))
+        .start();
+    step_to(&mut replayed, game.steps());
+
+    assert_eq!(
+        bits(replayed.paddle(Paddle::Right)),
+        bits(game.paddle(Paddle::Right))
+    );
+    assert_eq!(bits(replayed.ball().0), bits(game.ball().0));
+}
+
+#[test]
+fn saved_replays_get_own_files_and_old_ones_are_deleted() {
+    let dir = std::env::temp_dir().join(format!("pong_replays_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let replay = classic_game().replay();
+
+    // the replays are saved in the same second, but none replaces another
+    let paths: Vec<PathBuf> = (0..MAX_REPLAYS + 2)
+        .map(|_| write_replay(&dir, &replay).unwrap())
+        .collect();
+    let kept = replay_files(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(kept.len(), MAX_REPLAYS);
+    assert_eq!(kept[0], paths[paths.len() - 1]);
+    assert!(!kept.contains(&paths[0]));
+    assert!(!kept.contains(&paths[1]));
+}
+
+#[test]
+fn paddle_hits_give_points() {
+    let mut game = classic_game();
+    let paddle = game.paddle(Paddle::Bottom);
+    game.set_ball(paddle + Vec2::new(0.0, 60.0), Vec2::new(0.0, -500.0));
+
+    game.step(20);
+
+    let (_, velocity) = game.ball();
+    assert!(velocity.y > 0.0, "ball did not bounce off the paddle");
+    assert_eq!(game.events().paddle_hits, 1);
+    assert_eq!(game.score().points, 1);
+}
+
+#[test]
+fn bounce_angle_depends_on_the_hit_position() {
+    let paddle_box = Aabb2d::new(Vec2::ZERO, Vec2::new(50.0, 15.0));
+
+    // the middle of the paddle sends the ball straight back
+    let middle = paddle_bounce(Paddle::Bottom, Vec2::new(0.0, 30.0), paddle_box, 0.0);
+    assert!(middle.distance(Vec2::Y) < 1e-6);
+
+    // the edges give the biggest angle, a moving paddle can not make it bigger
+    let edge = Vec2::new(MAX_BOUNCE_ANGLE.sin(), MAX_BOUNCE_ANGLE.cos());
+    for intent in [0.0, 1.0] {
+        let right = paddle_bounce(Paddle::Bottom, Vec2::new(50.0, 30.0), paddle_box, intent);
+        assert!(right.distance(edge) < 1e-6);
+    }
+    let left = paddle_bounce(Paddle::Bottom, Vec2::new(-50.0, 30.0), paddle_box, 0.0);
+    assert!(left.distance(Vec2::new(-edge.x, edge.y)) < 1e-6);
+
+    // the right paddle sends the ball straight to the left
+    let side_box = Aabb2d::new(Vec2::ZERO, Vec2::new(15.0, 50.0));
+    let side = paddle_bounce(Paddle::Right, Vec2::new(-30.0, 0.0), side_box, 0.0);
+    assert!(side.distance(Vec2::NEG_X) < 1e-6);
+}
+
+#[test]
+fn paddle_hits_speed_the_ball_up_to_the_limit() {
+    let mut game = classic_game();
+    let paddle = game.paddle(Paddle::Bottom);
+    game.set_ball(paddle + Vec2::new(0.0, 60.0), Vec2::new(0.0, -BALL_SPEED));
+    assert!(game.step_until(20, |game| game.events().paddle_hits == 1));
+    let (_, velocity) = game.ball();
+    assert_eq!(velocity.x, 0.0, "a hit in the middle goes straight back");
+    assert!((velocity.length() - BALL_SPEED * BALL_SPEED_UP).abs() < 1e-2);
+
+    // a ball at the limit does not get faster
+    let limit = BALL_SPEED * MAX_BALL_SPEED_FACTOR;
+    game.set_ball(paddle + Vec2::new(0.0, 60.0), Vec2::new(0.0, -limit * 0.99));
+    assert!(game.step_until(20, |game| game.events().paddle_hits == 2));
+    let (_, velocity) = game.ball();
+    assert!((velocity.length() - limit).abs() < 1e-2);
+}
+
+#[test]
+fn lost_balls_end_the_game() {
+    let mut game = classic_game();
+
+    for lives in (0..LIVES).rev() {
+        // the ball falls beside the paddle into the bottom wall
+        game.set_ball(Vec2::new(150.0, 0.0), Vec2::new(0.0, -500.0));
+        assert!(game.step_until(60, |game| game.score().lives == lives));
+    }
+
+    assert_eq!(game.events().balls_lost, LIVES);
+    assert_eq!(game.state(), PongState::GameOver);
+}
+
+/// steps a power-up lasts
+const POWER_UP_STEPS: u32 = (POWER_UP_DURATION as f64 * SIMULATION_HZ) as u32;
+
+#[test]
+fn multi_ball_ends_the_match_with_the_last_ball() {
+    let mut game = classic_game();
+    game.catch_power_up(Paddle::Bottom, PowerUp::MultiBall);
+    game.step(1);
+    assert_eq!(game.ball_count(), 3);
+
+    // the balls fall one after another beside the paddle into the bottom wall
+    game.set_balls(&[
+        (Vec2::new(150.0, 0.0), Vec2::new(0.0, -500.0)),
+        (Vec2::new(150.0, 50.0), Vec2::new(0.0, -500.0)),
+        (Vec2::new(150.0, 100.0), Vec2::new(0.0, -500.0)),
+    ]);
+    game.step(40);
+    assert_eq!(game.ball_count(), 2);
+    assert_eq!(game.score().lives, LIVES);
+    assert_eq!(game.events().balls_lost, 0);
+
+    assert!(game.step_until(60, |game| game.score().lives == LIVES - 1));
+    assert_eq!(game.events().balls_lost, 1);
+    assert_eq!(game.ball_count(), 1);
+}
+
+#[test]
+fn paddle_size_power_ups_expire() {
+    let mut game = classic_game();
+    let width = game.paddle_width(Paddle::Bottom);
+    // the ball rests in the middle, so nothing else is dropped or lost
+    game.set_ball(Vec2::new(0.0, 50.0), Vec2::ZERO);
+
+    game.catch_power_up(Paddle::Bottom, PowerUp::WidePaddle);
+    game.step(1);
+    assert_eq!(
+        game.paddle_width(Paddle::Bottom),
+        width * WIDE_PADDLE_FACTOR
+    );
+
+    // a narrow paddle replaces the wide one
+    game.catch_power_up(Paddle::Bottom, PowerUp::NarrowPaddle);
+    game.step(1);
+    let narrow = game.paddle_width(Paddle::Bottom);
+    assert!(narrow < width);
+
+    game.step(POWER_UP_STEPS - 2);
+    assert_eq!(game.paddle_width(Paddle::Bottom), narrow);
+    game.step(2);
+    assert_eq!(game.paddle_width(Paddle::Bottom), width);
+}
+
+#[test]
+fn slow_ball_expires() {
+    let mut game = classic_game();
+    let velocity = Vec2::new(100.0, 0.0);
+    let distance = |game: &mut HeadlessPong| {
+        game.set_ball(Vec2::ZERO, velocity);
+        game.step(12);
+        let distance = game.ball().0.x;
+        game.set_ball(Vec2::new(0.0, 50.0), Vec2::ZERO);
+        distance
+    };
+
+    let normal = distance(&mut game);
+    assert!((normal - velocity.x * 12.0 * STEP).abs() < 1e-2);
+
+    game.catch_power_up(Paddle::Bottom, PowerUp::SlowBall);
+    game.step(1);
+    assert!((distance(&mut game) - normal * SLOW_BALL_FACTOR).abs() < 1e-2);
+
+    game.step(POWER_UP_STEPS);
+    assert!((distance(&mut game) - normal).abs() < 1e-2);
+}
+
+#[test]
+fn sticky_paddle_holds_the_ball_until_it_expires() {
+    let mut game = classic_game();
+    let hold_steps = (STICKY_HOLD_TIME as f64 * SIMULATION_HZ) as u32;
+    let drop = |game: &mut HeadlessPong| {
+        game.set_ball(Vec2::new(0.0, 0.0), Vec2::new(0.0, -300.0));
+        let hits = game.events().paddle_hits;
+        assert!(game.step_until(60, |game| game.events().paddle_hits > hits));
+        game.ball().0
+    };
+
+    game.catch_power_up(Paddle::Bottom, PowerUp::StickyPaddle);
+    game.step(1);
+    let held = drop(&mut game);
+    game.step(hold_steps - 2);
+    assert_eq!(game.ball().0, held);
+    game.step(4);
+    assert!(game.ball().0.y > held.y);
+
+    // the ball rests until the power-up is over, then bounces off at once
+    game.set_ball(Vec2::new(0.0, 50.0), Vec2::ZERO);
+    game.step(POWER_UP_STEPS);
+    let bounced = drop(&mut game);
+    game.step(2);
+    assert!(game.ball().0.y > bounced.y);
+}
+
+#[test]
+fn rules_replace_the_default_lives() {
+    let rules = MatchRules {
+        lives: 1,
+        ..default()
+    };
+    let mut game = HeadlessPong::new(PongMode::Classic, PongLevel::default(), ARENA)
+        .with_resource(rules)
+        .start();
+    assert_eq!(game.score().lives, 1);
+
+    game.set_ball(Vec2::new(150.0, 0.0), Vec2::new(0.0, -500.0));
+    assert!(game.step_until(60, |game| game.state() == PongState::GameOver));
+}
+
+#[test]
+fn rules_are_clamped_to_the_limits() {
+    let mut rules = MatchRules {
+        lives: 0,
+        points_to_win: 0,
+        ball_speed: 1000.0,
+        paddle_size: f32::NAN,
+        solid_top_wall: false,
+    };
+
+    rules.clamp();
+
+    assert_eq!(rules.lives, 1);
+    assert_eq!(rules.points_to_win, 1);
+    assert_eq!(rules.ball_speed, 2.0);
+    assert_eq!(rules.paddle_size, 1.0);
+    assert!(!rules.solid_top_wall);
+}
+
+#[test]
+fn fallback_levels_fit_the_mode() {
+    assert_eq!(
+        PongLevel::for_mode(PongMode::Classic).exit_walls,
+        [Wall::Bottom]
+    );
+    assert!(!PongLevel::for_mode(PongMode::Breakout).bricks.is_empty());
+    assert_eq!(
+        PongLevel::for_mode(PongMode::Versus).exit_walls,
+        [Wall::Left, Wall::Right]
+    );
+}
+
+/// a level file of the mode with the rows of bricks
+fn level_file(mode: &str, bricks: &str) -> String {
+    format!(
+        "(mode: {mode}, ball_speed: 500.0, paddle_width: 100.0, exit_walls: [Bottom], bricks: [{bricks}])"
+    )
+}
+
+#[test]
+fn breakout_levels_need_bricks() {
+    let empty = r#""...", "000""#;
+    assert!(PongLevel::from_ron(level_file("Breakout", empty).as_bytes()).is_err());
+    assert!(PongLevel::from_ron(level_file("Classic", empty).as_bytes()).is_ok());
+
+    let bricks = level_file("Breakout", r#"".1.""#);
+    assert!(PongLevel::from_ron(bricks.as_bytes()).unwrap().has_bricks());
+}
+
+#[test]
+fn goals_count_for_the_other_player() {
+    let mut game = versus_game();
+
+    for goals in 1..=POINTS_TO_WIN {
+        // the ball flies above the left paddle into the left wall
+        game.set_ball(Vec2::new(0.0, 100.0), Vec2::new(-500.0, 0.0));
+        assert!(game.step_until(60, |game| game.versus_score().right == goals));
+        assert_eq!(game.versus_score().left, 0);
+    }
+
+    assert_eq!(game.state(), PongState::GameOver);
+}
+
+/// a versus game with the cpu on the right paddle
+fn cpu_game(difficulty: Difficulty) -> HeadlessPong {
+    HeadlessPong::new(PongMode::Versus, versus_level(), ARENA)
+        .with_resource(PaddleControllers::against_cpu(CpuSide::Right, difficulty))
+        .start()
+}
+
+#[test]
+fn prediction_folds_wall_bounces_back() {
+    // without walls the ball would reach the line at 150, the wall at 100 sends it back to 50
+    let prediction = predict_ball(Vec2::ZERO, Vec2::new(150.0, 100.0), 100.0, (-100.0, 100.0));
+    let (time, position) = prediction.unwrap();
+    assert!((time - 1.0).abs() < 1e-6);
+    assert!((position - 50.0).abs() < 1e-3);
+
+    // a ball flying away never reaches the line
+    assert!(predict_ball(Vec2::ZERO, Vec2::new(150.0, -100.0), 100.0, (-100.0, 100.0)).is_none());
+}
+
+#[test]
+fn hard_cpu_returns_a_straight_shot() {
+    let mut game = cpu_game(Difficulty::Hard);
+    // the ball flies above the left paddle straight to the right paddle
+    game.set_ball(Vec2::new(-50.0, 100.0), Vec2::new(500.0, 0.0));
+
+    assert!(game.step_until(120, |game| game.events().paddle_hits == 1));
+    let (_, velocity) = game.ball();
+    assert!(velocity.x < 0.0, "the cpu did not return the ball");
+    assert_eq!(game.versus_score().left, 0);
+}
+
+#[test]
+fn hard_cpu_returns_a_ball_after_a_wall_bounce() {
+    let mut game = cpu_game(Difficulty::Hard);
+    // the ball bounces off the top wall before it reaches the right paddle
+    game.set_ball(Vec2::new(-50.0, 0.0), Vec2::new(300.0, 600.0));
+
+    assert!(game.step_until(120, |game| game.events().paddle_hits == 1));
+    assert_eq!(game.events().object_hits, 1);
+    let (_, velocity) = game.ball();
+    assert!(velocity.x < 0.0, "the cpu did not return the ball");
+    assert_eq!(game.versus_score().left, 0);
+}
+
+#[test]
+fn easy_cpu_reacts_later_than_hard() {
+    let mut hard = cpu_game(Difficulty::Hard);
+    let mut easy = cpu_game(Difficulty::Easy);
+    for game in [&mut hard, &mut easy] {
+        game.set_ball(Vec2::new(-50.0, 100.0), Vec2::new(500.0, 0.0));
+        game.step(12);
+    }
+
+    // after a tenth of a second only the hard cpu has seen the ball
+    assert!(hard.paddle(Paddle::Right).y > 0.0);
+    assert_eq!(easy.paddle(Paddle::Right).y, 0.0);
+}
+
+#[test]
+fn network_client_follows_the_host() {
+    let host_connection = NetConnection::host(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+    let address = host_connection.local_addr().unwrap();
+    let client_connection = NetConnection::join(address).unwrap();
+    // the host gets the hello of the client while it starts
+    let mut host = network_game(host_connection).start();
+    let mut client = network_game(client_connection).start();
+
+    // the client moves its own paddle and the host applies the inputs
+    client.press(KeyCode::ArrowUp);
+    step_both(&mut host, &mut client, 30);
+    client.release(KeyCode::ArrowUp);
+    step_both(&mut host, &mut client, 10);
+    // the client gets the state after the last step of the host
+    client.step(1);
+
+    let host_paddle = host.paddle(Paddle::Right);
+    assert!(
+        host_paddle.y > 0.0,
+        "the host did not move the client paddle"
+    );
+    assert!(
+        client.paddle(Paddle::Right).distance(host_paddle) < 0.01,
+        "the prediction of the client differs from the host"
+    );
+    assert_eq!(client.paddle(Paddle::Left), host.paddle(Paddle::Left));
+    // the client moved the ball one step further than the host
+    let (client_ball, _) = client.ball();
+    let (host_ball, velocity) = host.ball();
+    assert!(client_ball.distance(host_ball) <= velocity.length() * STEP + 0.01);
+    assert_eq!(
+        (client.versus_score().left, client.versus_score().right),
+        (host.versus_score().left, host.versus_score().right)
+    );
+}
+
+#[test]
+fn host_stops_the_paddle_of_a_silent_client() {
+    let host_connection = NetConnection::host(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+    let address = host_connection.local_addr().unwrap();
+    let client_connection = NetConnection::join(address).unwrap();
+    let mut host = network_game(host_connection).start();
+    let mut client = network_game(client_connection).start();
+
+    client.press(KeyCode::ArrowUp);
+    step_both(&mut host, &mut client, 10);
+    // the paused client sends no more inputs
+    client.set_state(PongState::Paused);
+    client.update();
+    host.step(30);
+    let stopped = host.paddle(Paddle::Right);
+    host.step(10);
+
+    assert!(stopped.y > 0.0, "the host did not move the client paddle");
+    assert_eq!(host.paddle(Paddle::Right), stopped);
+}
+
+#[test]
+fn network_client_pauses_with_the_host() {
+    let host_connection = NetConnection::host(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+    let address = host_connection.local_addr().unwrap();
+    let client_connection = NetConnection::join(address).unwrap();
+    let mut host = network_game(host_connection).start();
+    let mut client = network_game(client_connection).start();
+    step_both(&mut host, &mut client, 5);
+
+    host.set_state(PongState::Paused);
+    host.update();
+    assert!(update_until(&mut client, |client| client.state() == PongState::Paused));
+
+    host.set_state(PongState::Game);
+    host.update();
+    assert!(update_until(&mut client, |client| client.state() == PongState::Game));
+
+    // a pause of the client is its own
+    client.set_state(PongState::Paused);
+    client.update();
+    host.step(5);
+    thread::sleep(Duration::from_millis(10));
+    client.update();
+    assert_eq!(client.state(), PongState::Paused);
+    assert_eq!(host.state(), PongState::Game);
+}
+
+#[test]
+fn network_client_gets_its_own_rules_back() {
+    let host_connection = NetConnection::host(SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+    let address = host_connection.local_addr().unwrap();
+    let client_connection = NetConnection::join(address).unwrap();
+    let host_rules = MatchRules {
+        points_to_win: 1,
+        ..default()
+    };
+    let client_rules = MatchRules {
+        lives: 5,
+        ..default()
+    };
+    let mut host = network_game(host_connection).with_resource(host_rules.clone());
+    let mut client = network_game(client_connection).with_resource(client_rules.clone());
+
+    // the lobby, the client gets the rules of the host with the welcome
+    for _ in 0..100 {
+        host.update();
+        client.update();
+        if client.app.world().resource::<NetConnection>().connected {
+            break;
+        }
+        thread::sleep(Duration::from_millis(1));
+    }
+    let mut host = host.start();
+    let mut client = client.start();
+    assert_eq!(*client.rules(), host_rules);
+
+    // a goal for the right player ends the game
+    host.set_ball(Vec2::new(-150.0, 120.0), Vec2::new(-500.0, 0.0));
+    assert!(host.step_until(60, |host| host.state() == PongState::GameOver));
+    assert!(update_until(&mut client, |client| {
+        client.state() == PongState::GameOver
+    }));
+
+    client.quit();
+    assert_eq!(*client.rules(), client_rules);
+}
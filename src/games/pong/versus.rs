@@ -0,0 +1,116 @@
+/// scoring of the two player versus mode
+use bevy::prelude::*;
+
+use crate::games::pong::{
+    game::{BallLost, OnGameScreen, PongMode, PongSimulation, PongState, Wall, simulation_running},
+    rules::MatchRules,
+};
+
+// versus constanten
+/// points to win of the default rules
+pub const POINTS_TO_WIN: u32 = 5;
+
+/// goals of the left and the right player
+#[derive(Resource, Default)]
+pub struct VersusScore {
+    pub left: u32,
+    pub right: u32,
+}
+
+impl VersusScore {
+    /// the side which reached the points to win
+    pub fn winner(&self, points_to_win: u32) -> Option<Wall> {
+        if self.left >= points_to_win {
+            Some(Wall::Left)
+        } else if self.right >= points_to_win {
+            Some(Wall::Right)
+        } else {
+            None
+        }
+    }
+}
+
+/// the text that shows the goals while playing
+#[derive(Component)]
+struct VersusScoreText;
+
+/// plugin for the versus mode
+pub struct VersusPlugin;
+impl Plugin for VersusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<VersusScore>()
+            .add_systems(
+                OnEnter(PongState::Loading),
+                versus_score_setup.run_if(resource_equals(PongMode::Versus)),
+            )
+            .add_systems(
+                FixedUpdate,
+                goal.after(PongSimulation)
+                    .run_if(simulation_running)
+                    .run_if(resource_equals(PongMode::Versus)),
+            )
+            .add_systems(
+                Update,
+                update_versus_score_text
+                    .run_if(in_state(PongState::Game))
+                    .run_if(resource_equals(PongMode::Versus)),
+            );
+    }
+}
+
+/// spawns the goal text on top of the game surface
+fn versus_score_setup(mut commands: Commands, score: Res<VersusScore>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        OnGameScreen,
+        children![(
+            Text::new(versus_score_text(&score)),
+            TextFont {
+                font_size: 40.0,
+                ..default()
+            },
+            VersusScoreText,
+        )],
+    ));
+}
+
+/// a ball on the left wall is a goal for the right player and the other way around
+/// the game is over if one player has enough goals
+fn goal(
+    mut ball_lost: EventReader<BallLost>,
+    mut score: ResMut<VersusScore>,
+    mut pong_state: ResMut<NextState<PongState>>,
+    rules: Res<MatchRules>,
+) {
+    for BallLost(wall) in ball_lost.read() {
+        match wall {
+            Wall::Left => score.right += 1,
+            Wall::Right => score.left += 1,
+            Wall::Top | Wall::Bottom => {}
+        }
+        if score.winner(rules.points_to_win).is_some() {
+            pong_state.set(PongState::GameOver);
+        }
+    }
+}
+
+/// writes the current goals into the goal text
+fn update_versus_score_text(
+    score: Res<VersusScore>,
+    mut text: Single<&mut Text, With<VersusScoreText>>,
+) {
+    if score.is_changed() {
+        text.0 = versus_score_text(&score);
+    }
+}
+
+/// text for the goal display
+pub fn versus_score_text(score: &VersusScore) -> String {
+    format!("{} : {}", score.left, score.right)
+}
@@ -1,3 +1,4 @@
+pub mod audio;
 pub mod enums;
 pub mod functions;
 pub mod games;
@@ -5,17 +6,28 @@ pub mod loading_screen;
 pub mod menus;
 pub mod structs;
 
-use crate::{enums::GameState, games::boogle::game::BooglePlugin};
+use crate::{
+    enums::GameState,
+    games::boogle::{dictionary::SystemDictionaryPlugin, game::BooglePlugin},
+};
 
-use bevy::prelude::*;
+use bevy::{prelude::*, render::camera::ScalingMode};
 use bevy_framepace::{FramepacePlugin, FramepaceSettings, Limiter};
 
-use games::pong::game::PongPlugin;
+use audio::AudioSettingsPlugin;
+use games::pong::game::{ARENA_SIZE, PongPlugin};
 use loading_screen::LoadingScreenPlugin;
-use menus::{game_menu::GameMenuPlugin, main_menu::MainMenuPlugin};
+use menus::{
+    difficulty_menu::DifficultyMenuPlugin, game_menu::GameMenuPlugin,
+    highscore_menu::HighScoreMenuPlugin, main_menu::MainMenuPlugin,
+    network_menu::NetworkMenuPlugin, replay_menu::ReplayMenuPlugin, rules_menu::RulesMenuPlugin,
+    settings_menu::SettingsMenuPlugin,
+};
 
 fn main() {
     App::new()
+        // asset sources have to be registered before the asset plugin
+        .add_plugins(SystemDictionaryPlugin)
         // setup Plugins
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             primary_window: Some(Window {
@@ -34,8 +46,15 @@ fn main() {
             limiter: Limiter::from_framerate(60.0),
         })
         //rest for plugins
+        .add_plugins(AudioSettingsPlugin)
         .add_plugins(MainMenuPlugin)
         .add_plugins(GameMenuPlugin)
+        .add_plugins(DifficultyMenuPlugin)
+        .add_plugins(ReplayMenuPlugin)
+        .add_plugins(RulesMenuPlugin)
+        .add_plugins(HighScoreMenuPlugin)
+        .add_plugins(NetworkMenuPlugin)
+        .add_plugins(SettingsMenuPlugin)
         .add_plugins(PongPlugin)
         .add_plugins(BooglePlugin)
         .add_plugins(LoadingScreenPlugin)
@@ -45,6 +64,16 @@ fn main() {
 }
 
 /// spawns the game Camera
+/// the camera shows at least the whole pong arena at every window size
 fn setup(mut commands: Commands) {
-    commands.spawn(Camera2d);
+    commands.spawn((
+        Camera2d,
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::AutoMin {
+                min_width: ARENA_SIZE.x,
+                min_height: ARENA_SIZE.y,
+            },
+            ..OrthographicProjection::default_2d()
+        }),
+    ));
 }
@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+
+use crate::functions::despawn_screen;
+use crate::games::pong::ai::{CpuSide, Difficulty};
+use crate::menus::{MenuButtonAction, MenuState, NORMAL_BUTTON, menu_button_node};
+/// all elemnts on difficulty screen need this
+#[derive(Component)]
+struct OnDifficultyScreen;
+
+/// the text of the button which changes the paddles of the cpu
+#[derive(Component)]
+struct CpuSideText;
+
+/// plugin for the DifficultyMenu
+pub struct DifficultyMenuPlugin;
+impl Plugin for DifficultyMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CpuSide>()
+            .add_systems(OnEnter(MenuState::Difficulty), difficulty_menu_setup)
+            .add_systems(
+                OnExit(MenuState::Difficulty),
+                despawn_screen::<OnDifficultyScreen>,
+            )
+            .add_systems(
+                Update,
+                update_cpu_side_text
+                    .run_if(in_state(MenuState::Difficulty))
+                    .run_if(resource_changed::<CpuSide>),
+            );
+    }
+}
+
+/// text of the button which changes the paddles of the cpu
+fn cpu_side_text(side: CpuSide) -> String {
+    format!("CPU: {}", side.name())
+}
+
+/// spawns the difficulty selection for the cpu opponent
+/// and the choice which paddles the cpu moves
+fn difficulty_menu_setup(mut commands: Commands, cpu_side: Res<CpuSide>) {
+    let button_node = menu_button_node();
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        OnDifficultyScreen,
+        children![(
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    MenuButtonAction::NextCpuSide,
+                    children![(Text::new(cpu_side_text(*cpu_side)), CpuSideText)]
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    MenuButtonAction::PongCpu(Difficulty::Easy),
+                    children![Text::new("Easy")]
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    MenuButtonAction::PongCpu(Difficulty::Medium),
+                    children![Text::new("Medium")]
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    MenuButtonAction::PongCpu(Difficulty::Hard),
+                    children![Text::new("Hard")]
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    MenuButtonAction::Back,
+                    children![Text::new("Back")]
+                )
+            ]
+        )],
+    ));
+}
+
+/// writes the paddles of the cpu into the button
+fn update_cpu_side_text(cpu_side: Res<CpuSide>, mut texts: Query<&mut Text, With<CpuSideText>>) {
+    for mut text in &mut texts {
+        text.0 = cpu_side_text(*cpu_side);
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
 use crate::functions::despawn_screen;
+use crate::games::pong::game::PongMode;
 use crate::menus::{MenuButtonAction, MenuState, NORMAL_BUTTON, menu_button_node};
 /// all elemnts on game screen need this
 #[derive(Component)]
@@ -28,33 +29,87 @@ fn game_menu_setup(mut commands: Commands) {
             ..default()
         },
         OnGameScreen,
+        // two columns, so all buttons fit into the window
         children![(
             Node {
-                flex_direction: FlexDirection::Column,
+                flex_direction: FlexDirection::Row,
                 align_items: AlignItems::Center,
                 ..default()
             },
             children![
                 (
-                    Button,
-                    button_node.clone(),
-                    BackgroundColor(NORMAL_BUTTON),
-                    MenuButtonAction::Pong,
-                    children![Text::new("Pong"),]
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    children![
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Pong(PongMode::Classic),
+                            children![Text::new("Pong")]
+                        ),
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Pong(PongMode::Breakout),
+                            children![Text::new("Breakout")]
+                        ),
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Pong(PongMode::Versus),
+                            children![Text::new("Pong versus")]
+                        ),
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::CpuDifficulty,
+                            children![Text::new("Pong vs CPU")]
+                        )
+                    ]
                 ),
                 (
-                    Button,
-                    button_node.clone(),
-                    BackgroundColor(NORMAL_BUTTON),
-                    MenuButtonAction::Boogle,
-                    children![Text::new("Boogle")]
-                ),
-                (
-                    Button,
-                    button_node.clone(),
-                    BackgroundColor(NORMAL_BUTTON),
-                    MenuButtonAction::Back,
-                    children![Text::new("Back")]
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        ..default()
+                    },
+                    children![
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Network,
+                            children![Text::new("Pong LAN")]
+                        ),
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Replays,
+                            children![Text::new("Replays")]
+                        ),
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Boogle,
+                            children![Text::new("Boogle")]
+                        ),
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Back,
+                            children![Text::new("Back")]
+                        )
+                    ]
                 )
             ]
         )],
@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+
+use crate::functions::despawn_screen;
+use crate::games::pong::highscore::{HighScoreCategory, HighScores};
+use crate::menus::{MenuButtonAction, MenuState, NORMAL_BUTTON, menu_button_node};
+
+/// all elemnts on high score screen need this
+#[derive(Component)]
+struct OnHighScoreScreen;
+
+/// button which shows the table of its category
+#[derive(Component)]
+struct CategoryButton(HighScoreCategory);
+
+/// the text with the entries of the shown table
+#[derive(Component)]
+struct HighScoreTableText;
+
+/// the category of the shown table
+#[derive(Resource)]
+struct ShownCategory(HighScoreCategory);
+
+impl Default for ShownCategory {
+    fn default() -> Self {
+        Self(HighScoreCategory::Classic)
+    }
+}
+
+/// plugin for the HighScoreMenu
+pub struct HighScoreMenuPlugin;
+impl Plugin for HighScoreMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShownCategory>()
+            .add_systems(OnEnter(MenuState::HighScores), highscore_menu_setup)
+            .add_systems(
+                OnExit(MenuState::HighScores),
+                despawn_screen::<OnHighScoreScreen>,
+            )
+            .add_systems(
+                Update,
+                (category_action, update_table_text)
+                    .chain()
+                    .run_if(in_state(MenuState::HighScores)),
+            );
+    }
+}
+
+/// rank, name, points and time of every entry of the table
+fn table_text(high_scores: &HighScores, category: HighScoreCategory) -> String {
+    let table = high_scores.table(category);
+    let mut text = format!("{}\n", category.name());
+    if table.is_empty() {
+        text.push_str("No high scores yet");
+    }
+    for (rank, entry) in table.iter().enumerate() {
+        text.push_str(&format!(
+            "\n{:>2}. {:<12} {:>5} {:>7.1} s",
+            rank + 1,
+            entry.name,
+            entry.points,
+            entry.time
+        ));
+    }
+    text
+}
+
+/// spawns a button for every category and the shown table
+fn highscore_menu_setup(
+    mut commands: Commands,
+    high_scores: Res<HighScores>,
+    shown: Res<ShownCategory>,
+) {
+    let button_node = menu_button_node();
+    let small_button_node = Node {
+        width: Val::Px(170.0),
+        height: Val::Px(50.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        ..button_node.clone()
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnHighScoreScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for category in HighScoreCategory::ALL {
+                                parent.spawn((
+                                    Button,
+                                    small_button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    CategoryButton(category),
+                                    children![Text::new(category.name())],
+                                ));
+                            }
+                        });
+                    parent.spawn((
+                        Node {
+                            min_height: Val::Px(330.0),
+                            margin: UiRect::all(Val::Px(10.0)),
+                            ..default()
+                        },
+                        children![(
+                            Text::new(table_text(&high_scores, shown.0)),
+                            HighScoreTableText,
+                        )],
+                    ));
+                    parent.spawn((
+                        Button,
+                        button_node.clone(),
+                        BackgroundColor(NORMAL_BUTTON),
+                        MenuButtonAction::Back,
+                        children![Text::new("Back")],
+                    ));
+                });
+        });
+}
+
+/// shows the table of the pressed category
+#[allow(clippy::type_complexity)]
+fn category_action(
+    interaction_query: Query<(&Interaction, &CategoryButton), (Changed<Interaction>, With<Button>)>,
+    mut shown: ResMut<ShownCategory>,
+) {
+    for (interaction, CategoryButton(category)) in &interaction_query {
+        if *interaction == Interaction::Pressed {
+            shown.0 = *category;
+        }
+    }
+}
+
+/// writes the entries of the shown table into the text
+fn update_table_text(
+    high_scores: Res<HighScores>,
+    shown: Res<ShownCategory>,
+    mut text: Single<&mut Text, With<HighScoreTableText>>,
+) {
+    if !high_scores.is_changed() && !shown.is_changed() {
+        return;
+    }
+    text.0 = table_text(&high_scores, shown.0);
+}
@@ -64,6 +64,28 @@ fn main_menu_setup(mut commands: Commands) {
                             parent.spawn(Text::new("Game selection"));
                         });
 
+                    parent
+                        .spawn((
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::HighScores,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(Text::new("High scores"));
+                        });
+
+                    parent
+                        .spawn((
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Settings,
+                        ))
+                        .with_children(|parent| {
+                            parent.spawn(Text::new("Settings"));
+                        });
+
                     parent
                         .spawn((
                             Button,
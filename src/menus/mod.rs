@@ -1,18 +1,44 @@
-use bevy::prelude::*;
+use std::path::PathBuf;
+
+use bevy::{
+    input::mouse::{MouseScrollUnit, MouseWheel},
+    picking::hover::HoverMap,
+    prelude::*,
+};
 
 use crate::enums::GameState;
+use crate::games::pong::{
+    ai::{CpuSide, Difficulty, PaddleControllers},
+    game::PongMode,
+    replay::{ReplayPlayer, load_replay},
+    rules::MatchRules,
+};
 
+pub mod difficulty_menu;
 pub mod game_menu;
+pub mod highscore_menu;
 pub mod main_menu;
+pub mod network_menu;
+pub mod replay_menu;
+pub mod rules_menu;
+pub mod settings_menu;
 
 pub const NORMAL_BUTTON: Color = Color::srgb(0.5, 0.5, 0.5);
 pub const HOVERED_BUTTON: Color = Color::srgb(0.9, 0.9, 0.9);
+/// pixels one line of the mouse wheel scrolls
+const SCROLL_LINE_HEIGHT: f32 = 21.0;
 
 /// different menu states
 #[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
 pub enum MenuState {
     Main,
     Games,
+    Difficulty,
+    Rules,
+    Network,
+    Replays,
+    HighScores,
+    Settings,
     #[default]
     Disabled,
 }
@@ -21,8 +47,17 @@ pub enum MenuState {
 #[derive(Component)]
 pub enum MenuButtonAction {
     Games,
-    Pong,
+    Pong(PongMode),
+    CpuDifficulty,
+    /// changes the paddles the cpu moves
+    NextCpuSide,
+    PongCpu(Difficulty),
+    Network,
+    Replays,
+    Replay(PathBuf),
+    HighScores,
     Boogle,
+    Settings,
     Back,
     Quit,
 }
@@ -42,6 +77,7 @@ pub fn menu_button_node() -> Node {
 }
 
 /// change the collors of the button if pressed or hovered
+#[allow(clippy::type_complexity)]
 pub fn button_system(
     mut interaction_query: Query<
         (&Interaction, &mut BackgroundColor),
@@ -57,15 +93,43 @@ pub fn button_system(
     }
 }
 
+/// the mouse wheel scrolls the hovered nodes with Overflow::scroll_y
+/// text inside them must not block the lower nodes from the picking
+pub fn scroll_system(
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    hover_map: Res<HoverMap>,
+    mut scroll_query: Query<(&Node, &mut ScrollPosition)>,
+) {
+    for event in mouse_wheel_events.read() {
+        let scroll = match event.unit {
+            MouseScrollUnit::Line => event.y * SCROLL_LINE_HEIGHT,
+            MouseScrollUnit::Pixel => event.y,
+        };
+        for pointer_map in hover_map.values() {
+            for entity in pointer_map.keys() {
+                if let Ok((node, mut scroll_position)) = scroll_query.get_mut(*entity)
+                    && node.overflow.y == OverflowAxis::Scroll
+                {
+                    scroll_position.offset_y -= scroll;
+                }
+            }
+        }
+    }
+}
+
 /// change the state depends on wich button is pressed
+#[allow(clippy::type_complexity)]
 pub fn button_action(
     interaction_query: Query<
         (&Interaction, &MenuButtonAction),
         (Changed<Interaction>, With<Button>),
     >,
+    mut commands: Commands,
     mut app_exit: EventWriter<AppExit>,
     mut menu_state: ResMut<NextState<MenuState>>,
     mut game_state: ResMut<NextState<GameState>>,
+    rules: Res<MatchRules>,
+    mut cpu_side: ResMut<CpuSide>,
 ) {
     for (interaction, menu_button_action) in &interaction_query {
         if *interaction == Interaction::Pressed {
@@ -77,9 +141,43 @@ pub fn button_action(
                 MenuButtonAction::Games => {
                     menu_state.set(MenuState::Games);
                 }
-                MenuButtonAction::Pong => {
-                    menu_state.set(MenuState::Disabled);
-                    game_state.set(GameState::Pong);
+                MenuButtonAction::Pong(mode) => {
+                    commands.insert_resource(*mode);
+                    commands.insert_resource(PaddleControllers::default());
+                    menu_state.set(MenuState::Rules);
+                }
+                MenuButtonAction::CpuDifficulty => {
+                    menu_state.set(MenuState::Difficulty);
+                }
+                MenuButtonAction::NextCpuSide => *cpu_side = cpu_side.next(),
+                MenuButtonAction::PongCpu(difficulty) => {
+                    commands.insert_resource(PongMode::Versus);
+                    commands
+                        .insert_resource(PaddleControllers::against_cpu(*cpu_side, *difficulty));
+                    menu_state.set(MenuState::Rules);
+                }
+                MenuButtonAction::Network => {
+                    menu_state.set(MenuState::Network);
+                }
+                MenuButtonAction::Replays => {
+                    menu_state.set(MenuState::Replays);
+                }
+                MenuButtonAction::Replay(path) => match load_replay(path) {
+                    Ok(replay) => {
+                        commands.insert_resource(replay.mode);
+                        commands.insert_resource(replay.controllers.clone());
+                        commands.insert_resource(replay.rules.clone());
+                        commands.insert_resource(ReplayPlayer::new(replay, rules.clone()));
+                        menu_state.set(MenuState::Disabled);
+                        game_state.set(GameState::Pong);
+                    }
+                    Err(e) => eprintln!("{}", e),
+                },
+                MenuButtonAction::HighScores => {
+                    menu_state.set(MenuState::HighScores);
+                }
+                MenuButtonAction::Settings => {
+                    menu_state.set(MenuState::Settings);
                 }
                 MenuButtonAction::Boogle => {
                     menu_state.set(MenuState::Disabled);
@@ -0,0 +1,297 @@
+use std::net::{Ipv4Addr, SocketAddr, ToSocketAddrs};
+
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use crate::enums::GameState;
+use crate::functions::despawn_screen;
+use crate::games::pong::{
+    game::PongMode,
+    network::{DEFAULT_PORT, NetConnection, NetRole},
+};
+use crate::menus::{MenuButtonAction, MenuState, NORMAL_BUTTON, menu_button_node};
+
+// lobby constanten
+const MAX_ADDRESS_LENGTH: usize = 64;
+
+/// all elemnts on network screen need this
+#[derive(Component)]
+struct OnNetworkScreen;
+
+/// button actions of the lobby
+#[derive(Component)]
+enum NetworkButtonAction {
+    Host,
+    Join,
+}
+
+/// the text with the address of the host
+#[derive(Component)]
+struct AddressText;
+
+/// the text with the state of the connection
+#[derive(Component)]
+struct StatusText;
+
+/// address of the host the client joins, it is typed in the lobby
+#[derive(Resource)]
+struct JoinAddress(String);
+
+impl Default for JoinAddress {
+    fn default() -> Self {
+        Self(format!("{}:{}", Ipv4Addr::LOCALHOST, DEFAULT_PORT))
+    }
+}
+
+/// the last error in the lobby
+#[derive(Resource, Default)]
+struct LobbyError(Option<String>);
+
+/// plugin for the NetworkMenu
+pub struct NetworkMenuPlugin;
+impl Plugin for NetworkMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<JoinAddress>()
+            .init_resource::<LobbyError>()
+            .add_systems(OnEnter(MenuState::Network), network_menu_setup)
+            .add_systems(
+                OnExit(MenuState::Network),
+                (despawn_screen::<OnNetworkScreen>, close_unused_connection),
+            )
+            .add_systems(
+                Update,
+                (
+                    network_button_action,
+                    address_entry,
+                    update_status_text,
+                    start_network_game,
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::Network)),
+            );
+    }
+}
+
+/// text of the address while it is typed
+fn address_text(address: &str) -> String {
+    format!("Host address: {}_", address)
+}
+
+/// what the lobby is doing
+fn status_text(connection: Option<&NetConnection>, error: &LobbyError) -> String {
+    match (connection, &error.0) {
+        (None, Some(error)) => error.clone(),
+        (None, None) => "Host a game or join one".to_string(),
+        (Some(connection), _) => match (connection.role, connection.peer) {
+            (NetRole::Host, _) => match connection.local_addr() {
+                Ok(address) => format!("Waiting for a player on port {}", address.port()),
+                Err(e) => e.to_string(),
+            },
+            (NetRole::Client, Some(peer)) => format!("Connecting to {}", peer),
+            (NetRole::Client, None) => "Connecting".to_string(),
+        },
+    }
+}
+
+/// the address with the default port if it has none
+fn parse_address(text: &str) -> Option<SocketAddr> {
+    let text = text.trim();
+    text.to_socket_addrs()
+        .or_else(|_| (text, DEFAULT_PORT).to_socket_addrs())
+        .ok()?
+        .next()
+}
+
+/// spawns the status, the address and the buttons of the lobby
+fn network_menu_setup(
+    mut commands: Commands,
+    address: Res<JoinAddress>,
+    mut error: ResMut<LobbyError>,
+) {
+    error.0 = None;
+    let button_node = menu_button_node();
+
+    commands.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            align_items: AlignItems::Center,
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        OnNetworkScreen,
+        children![(
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                (
+                    Text::new("Pong in the local network"),
+                    TextFont {
+                        font_size: 40.0,
+                        ..default()
+                    },
+                ),
+                (
+                    Node {
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    children![(Text::new(status_text(None, &error)), StatusText)],
+                ),
+                (Text::new(address_text(&address.0)), AddressText),
+                (
+                    Node {
+                        flex_direction: FlexDirection::Row,
+                        ..default()
+                    },
+                    children![
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            NetworkButtonAction::Host,
+                            children![Text::new("Host")]
+                        ),
+                        (
+                            Button,
+                            button_node.clone(),
+                            BackgroundColor(NORMAL_BUTTON),
+                            NetworkButtonAction::Join,
+                            children![Text::new("Join")]
+                        ),
+                    ]
+                ),
+                (
+                    Button,
+                    button_node.clone(),
+                    BackgroundColor(NORMAL_BUTTON),
+                    MenuButtonAction::Back,
+                    children![Text::new("Back")]
+                ),
+            ]
+        )],
+    ));
+}
+
+/// opens the socket of the host or asks the host at the typed address for a game
+#[allow(clippy::type_complexity)]
+fn network_button_action(
+    mut commands: Commands,
+    interaction_query: Query<
+        (&Interaction, &NetworkButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    connection: Option<Res<NetConnection>>,
+    address: Res<JoinAddress>,
+    mut error: ResMut<LobbyError>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        // the port of the host is still open
+        if matches!(action, NetworkButtonAction::Host)
+            && connection
+                .as_ref()
+                .is_some_and(|connection| connection.role == NetRole::Host)
+        {
+            continue;
+        }
+
+        let result = match action {
+            NetworkButtonAction::Host => {
+                NetConnection::host((Ipv4Addr::UNSPECIFIED, DEFAULT_PORT).into())
+            }
+            NetworkButtonAction::Join => match parse_address(&address.0) {
+                Some(address) => NetConnection::join(address),
+                None => {
+                    commands.remove_resource::<NetConnection>();
+                    error.0 = Some(format!("Unknown address {}", address.0.trim()));
+                    continue;
+                }
+            },
+        };
+        match result {
+            Ok(connection) => {
+                error.0 = None;
+                commands.insert_resource(connection);
+            }
+            Err(e) => {
+                commands.remove_resource::<NetConnection>();
+                error.0 = Some(e.to_string());
+            }
+        }
+    }
+}
+
+/// typing changes the address of the host
+fn address_entry(
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut address: ResMut<JoinAddress>,
+    mut text: Single<&mut Text, With<AddressText>>,
+) {
+    for event in keyboard_events.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Backspace => {
+                address.0.pop();
+            }
+            _ => {
+                if let Some(input) = &event.text {
+                    for character in input.chars().filter(|character| !character.is_control()) {
+                        if address.0.chars().count() < MAX_ADDRESS_LENGTH {
+                            address.0.push(character);
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if address.is_changed() {
+        text.0 = address_text(&address.0);
+    }
+}
+
+fn update_status_text(
+    connection: Option<Res<NetConnection>>,
+    error: Res<LobbyError>,
+    mut text: Single<&mut Text, With<StatusText>>,
+) {
+    text.0 = status_text(connection.as_deref(), &error);
+}
+
+/// both sides start the versus game as soon as the host accepted the client
+fn start_network_game(
+    mut commands: Commands,
+    connection: Option<Res<NetConnection>>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let Some(connection) = connection else {
+        return;
+    };
+    if connection.connected {
+        commands.insert_resource(PongMode::Versus);
+        commands.insert_resource(connection.controllers());
+        menu_state.set(MenuState::Disabled);
+        game_state.set(GameState::Pong);
+    }
+}
+
+/// leaving the lobby without a game closes the socket
+fn close_unused_connection(mut commands: Commands, connection: Option<Res<NetConnection>>) {
+    if connection.is_some_and(|connection| !connection.connected) {
+        commands.remove_resource::<NetConnection>();
+    }
+}
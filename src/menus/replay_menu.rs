@@ -0,0 +1,78 @@
+use bevy::prelude::*;
+
+use crate::functions::despawn_screen;
+use crate::games::pong::replay::list_replays;
+use crate::menus::{MenuButtonAction, MenuState, NORMAL_BUTTON, menu_button_node};
+
+/// the menu shows at most this many of the newest replays
+const MAX_LISTED_REPLAYS: usize = 6;
+
+/// all elemnts on replay screen need this
+#[derive(Component)]
+struct OnReplayScreen;
+
+/// plugin for the ReplayMenu
+pub struct ReplayMenuPlugin;
+impl Plugin for ReplayMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Replays), replay_menu_setup)
+            .add_systems(OnExit(MenuState::Replays), despawn_screen::<OnReplayScreen>);
+    }
+}
+
+/// spawns a button for every recorded replay
+fn replay_menu_setup(mut commands: Commands) {
+    let button_node = menu_button_node();
+    let replays = list_replays();
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnReplayScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    if replays.is_empty() {
+                        parent.spawn(Text::new("No replays recorded"));
+                    }
+                    for path in replays.into_iter().take(MAX_LISTED_REPLAYS) {
+                        // the file name without the extension
+                        let name = path
+                            .file_name()
+                            .and_then(|name| name.to_str())
+                            .and_then(|name| name.split('.').next())
+                            .unwrap_or_default()
+                            .to_string();
+                        parent.spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(300.0),
+                                ..button_node.clone()
+                            },
+                            BackgroundColor(NORMAL_BUTTON),
+                            MenuButtonAction::Replay(path),
+                            children![Text::new(name)],
+                        ));
+                    }
+                    parent.spawn((
+                        Button,
+                        button_node.clone(),
+                        BackgroundColor(NORMAL_BUTTON),
+                        MenuButtonAction::Back,
+                        children![Text::new("Back")],
+                    ));
+                });
+        });
+}
@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+
+use crate::enums::GameState;
+use crate::functions::despawn_screen;
+use crate::games::pong::{
+    game::PongMode,
+    highscore::DEFAULT_RULES_HINT,
+    rules::{MatchRules, Rule, save_match_rules},
+};
+use crate::menus::{MenuButtonAction, MenuState, NORMAL_BUTTON, menu_button_node};
+
+/// all elemnts on rules screen need this
+#[derive(Component)]
+struct OnRulesScreen;
+
+/// button actions of the rules screen
+#[derive(Component, Clone, Copy, Eq, PartialEq, Debug)]
+enum RulesButtonAction {
+    Mode(PongMode),
+    Decrease(Rule),
+    Increase(Rule),
+    Start,
+}
+
+/// the text with the selected mode
+#[derive(Component)]
+struct ModeText;
+
+/// the text with the value of a rule
+#[derive(Component)]
+struct RuleText(Rule);
+
+/// the text which says that high scores need the default rules
+#[derive(Component)]
+struct RulesHintText;
+
+/// plugin for the RulesMenu
+pub struct RulesMenuPlugin;
+impl Plugin for RulesMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(MenuState::Rules), rules_menu_setup)
+            .add_systems(
+                OnExit(MenuState::Rules),
+                (despawn_screen::<OnRulesScreen>, save_rules),
+            )
+            .add_systems(
+                Update,
+                (rules_action, update_rules_text)
+                    .chain()
+                    .run_if(in_state(MenuState::Rules)),
+            );
+    }
+}
+
+fn mode_name(mode: PongMode) -> &'static str {
+    match mode {
+        PongMode::Classic => "Pong",
+        PongMode::Breakout => "Breakout",
+        PongMode::Versus => "Versus",
+    }
+}
+
+fn mode_text(mode: PongMode) -> String {
+    format!("Mode: {}", mode_name(mode))
+}
+
+fn rule_text(rules: &MatchRules, rule: Rule) -> String {
+    format!("{}: {}", rule.name(), rules.value_text(rule))
+}
+
+/// changed rules turn off the high scores
+fn rules_hint(rules: &MatchRules) -> &'static str {
+    if *rules == MatchRules::default() {
+        ""
+    } else {
+        DEFAULT_RULES_HINT
+    }
+}
+
+/// spawns the mode buttons, a row for every rule, the high score hint and the start button
+fn rules_menu_setup(mut commands: Commands, mode: Res<PongMode>, rules: Res<MatchRules>) {
+    let button_node = menu_button_node();
+    let small_button_node = Node {
+        width: Val::Px(170.0),
+        height: Val::Px(50.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        ..button_node.clone()
+    };
+    let step_button_node = Node {
+        width: Val::Px(50.0),
+        ..small_button_node.clone()
+    };
+    let text_node = Node {
+        width: Val::Px(300.0),
+        justify_content: JustifyContent::Center,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnRulesScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((Text::new(mode_text(*mode)), ModeText));
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            for mode in [PongMode::Classic, PongMode::Breakout, PongMode::Versus] {
+                                parent.spawn((
+                                    Button,
+                                    small_button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    RulesButtonAction::Mode(mode),
+                                    children![Text::new(mode_name(mode))],
+                                ));
+                            }
+                        });
+                    for rule in Rule::ALL {
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Button,
+                                    step_button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    RulesButtonAction::Decrease(rule),
+                                    children![Text::new("-")],
+                                ));
+                                parent.spawn((
+                                    text_node.clone(),
+                                    children![(Text::new(rule_text(&rules, rule)), RuleText(rule))],
+                                ));
+                                parent.spawn((
+                                    Button,
+                                    step_button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    RulesButtonAction::Increase(rule),
+                                    children![Text::new("+")],
+                                ));
+                            });
+                    }
+                    parent.spawn((Text::new(rules_hint(&rules)), RulesHintText));
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Button,
+                                button_node.clone(),
+                                BackgroundColor(NORMAL_BUTTON),
+                                RulesButtonAction::Start,
+                                children![Text::new("Start")],
+                            ));
+                            parent.spawn((
+                                Button,
+                                button_node.clone(),
+                                BackgroundColor(NORMAL_BUTTON),
+                                MenuButtonAction::Back,
+                                children![Text::new("Back")],
+                            ));
+                        });
+                });
+        });
+}
+
+/// changes the mode or a rule, start begins the game with the chosen rules
+#[allow(clippy::type_complexity)]
+fn rules_action(
+    interaction_query: Query<
+        (&Interaction, &RulesButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut mode: ResMut<PongMode>,
+    mut rules: ResMut<MatchRules>,
+    mut menu_state: ResMut<NextState<MenuState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match *action {
+            RulesButtonAction::Mode(new_mode) => *mode = new_mode,
+            RulesButtonAction::Decrease(rule) => rules.change(rule, false),
+            RulesButtonAction::Increase(rule) => rules.change(rule, true),
+            RulesButtonAction::Start => {
+                menu_state.set(MenuState::Disabled);
+                game_state.set(GameState::Pong);
+            }
+        }
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn update_rules_text(
+    mode: Res<PongMode>,
+    rules: Res<MatchRules>,
+    mut mode_texts: Query<&mut Text, With<ModeText>>,
+    mut rule_texts: Query<(&mut Text, &RuleText), Without<ModeText>>,
+    mut hint_text: Single<&mut Text, (With<RulesHintText>, Without<ModeText>, Without<RuleText>)>,
+) {
+    if mode.is_changed() {
+        for mut text in &mut mode_texts {
+            text.0 = mode_text(*mode);
+        }
+    }
+    if rules.is_changed() {
+        for (mut text, RuleText(rule)) in &mut rule_texts {
+            text.0 = rule_text(&rules, *rule);
+        }
+        hint_text.0 = rules_hint(&rules).to_string();
+    }
+}
+
+/// the rules are kept for the next start of the app
+fn save_rules(rules: Res<MatchRules>) {
+    if let Err(e) = save_match_rules(&rules) {
+        eprintln!("{}", e);
+    }
+}
@@ -0,0 +1,531 @@
+use bevy::{prelude::*, ui::RelativeCursorPosition};
+
+use crate::audio::{AudioSettings, VolumeChannel, save_audio_settings};
+use crate::functions::despawn_screen;
+use crate::games::boogle::{
+    dictionary::{DictionarySettings, save_dictionary_settings},
+    scoring::{ScoringRules, save_scoring_rules},
+};
+use crate::games::pong::{
+    effects::{Effect, EffectSettings, save_effect_settings},
+    game::Paddle,
+    input::{InputBindings, MAX_GAMEPADS, save_bindings},
+};
+use crate::menus::{MenuButtonAction, MenuState, NORMAL_BUTTON, menu_button_node};
+
+// settings constanten
+const SLIDER_WIDTH: f32 = 520.0;
+const SLIDER_COLOR: Color = Color::srgb(0.2, 0.2, 0.2);
+
+/// all elemnts on settings screen need this
+#[derive(Component)]
+struct OnSettingsScreen;
+
+/// button actions of the settings screen
+#[derive(Component, Clone, Copy, Eq, PartialEq, Debug)]
+enum SettingsButtonAction {
+    Device(Paddle),
+    NegativeKey(Paddle),
+    PositiveKey(Paddle),
+    Gamepad(Paddle),
+    Effect(Effect),
+    Language,
+    MinWordLength,
+    Scoring,
+}
+
+/// the text of a settings button
+#[derive(Component)]
+struct SettingsText(SettingsButtonAction);
+
+/// the bar of a volume, pressing or dragging on it sets the volume
+#[derive(Component)]
+struct VolumeSlider(VolumeChannel);
+
+/// the part of the bar which shows the volume
+#[derive(Component)]
+struct VolumeFill(VolumeChannel);
+
+/// the text with the volume in percent
+#[derive(Component)]
+struct VolumeText(VolumeChannel);
+
+/// the key binding which gets the next pressed key
+#[derive(Resource, Default)]
+struct AwaitingKey(Option<SettingsButtonAction>);
+
+/// plugin for the SettingsMenu
+pub struct SettingsMenuPlugin;
+impl Plugin for SettingsMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AwaitingKey>()
+            .add_systems(OnEnter(MenuState::Settings), settings_menu_setup)
+            .add_systems(
+                OnExit(MenuState::Settings),
+                (
+                    despawn_screen::<OnSettingsScreen>,
+                    cancel_rebind,
+                    save_volume,
+                ),
+            )
+            .add_systems(
+                Update,
+                (
+                    settings_action,
+                    rebind_key,
+                    update_settings_text,
+                    volume_slider,
+                    update_volume_sliders,
+                )
+                    .chain()
+                    .run_if(in_state(MenuState::Settings)),
+            );
+    }
+}
+
+/// name of the player of the paddle
+fn player_name(paddle: Paddle) -> &'static str {
+    match paddle {
+        Paddle::Bottom => "Solo player",
+        Paddle::Left => "Left player",
+        Paddle::Right => "Right player",
+    }
+}
+
+/// names of the negative and positive direction of the paddle
+fn direction_names(paddle: Paddle) -> (&'static str, &'static str) {
+    match paddle {
+        Paddle::Bottom => ("Left", "Right"),
+        Paddle::Left | Paddle::Right => ("Down", "Up"),
+    }
+}
+
+/// name of a key without the prefix of the letter keys
+fn key_name(key: KeyCode) -> String {
+    let name = format!("{:?}", key);
+    name.strip_prefix("Key").unwrap_or(&name).to_string()
+}
+
+/// text of the button with the current binding
+fn settings_text(
+    action: SettingsButtonAction,
+    bindings: &InputBindings,
+    effects: &EffectSettings,
+    dictionary: &DictionarySettings,
+    scoring: &ScoringRules,
+    awaiting: &AwaitingKey,
+) -> String {
+    if awaiting.0 == Some(action) {
+        return "Press a key".to_string();
+    }
+
+    match action {
+        SettingsButtonAction::Device(paddle) => bindings.get(paddle).device.name().to_string(),
+        SettingsButtonAction::NegativeKey(paddle) => format!(
+            "{}: {}",
+            direction_names(paddle).0,
+            key_name(bindings.get(paddle).negative)
+        ),
+        SettingsButtonAction::PositiveKey(paddle) => format!(
+            "{}: {}",
+            direction_names(paddle).1,
+            key_name(bindings.get(paddle).positive)
+        ),
+        SettingsButtonAction::Gamepad(paddle) => {
+            format!("Gamepad {}", bindings.get(paddle).gamepad + 1)
+        }
+        SettingsButtonAction::Effect(effect) => {
+            let state = if effects.enabled(effect) { "On" } else { "Off" };
+            format!("{}: {}", effect.name(), state)
+        }
+        SettingsButtonAction::Language => format!("Words: {}", dictionary.language.name()),
+        SettingsButtonAction::MinWordLength => format!("Min length: {}", scoring.min_word_length),
+        SettingsButtonAction::Scoring => format!("Points: {}", scoring.scheme.name()),
+    }
+}
+
+/// volume in percent
+fn volume_text(volume: f32) -> String {
+    format!("{:.0}%", volume * 100.0)
+}
+
+/// spawns a row with the bindings for every player, a row with the effects,
+/// a row with the boogle words and scoring and a slider for every volume
+fn settings_menu_setup(
+    mut commands: Commands,
+    bindings: Res<InputBindings>,
+    effects: Res<EffectSettings>,
+    dictionary: Res<DictionarySettings>,
+    scoring: Res<ScoringRules>,
+    audio: Res<AudioSettings>,
+    awaiting: Res<AwaitingKey>,
+) {
+    let button_node = menu_button_node();
+    let small_button_node = Node {
+        width: Val::Px(170.0),
+        height: Val::Px(50.0),
+        margin: UiRect::all(Val::Px(5.0)),
+        ..button_node.clone()
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            OnSettingsScreen,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for paddle in [Paddle::Bottom, Paddle::Left, Paddle::Right] {
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Node {
+                                        width: Val::Px(160.0),
+                                        ..default()
+                                    },
+                                    children![Text::new(player_name(paddle))],
+                                ));
+                                for action in [
+                                    SettingsButtonAction::Device(paddle),
+                                    SettingsButtonAction::NegativeKey(paddle),
+                                    SettingsButtonAction::PositiveKey(paddle),
+                                    SettingsButtonAction::Gamepad(paddle),
+                                ] {
+                                    parent.spawn((
+                                        Button,
+                                        small_button_node.clone(),
+                                        BackgroundColor(NORMAL_BUTTON),
+                                        action,
+                                        children![(
+                                            Text::new(settings_text(
+                                                action,
+                                                &bindings,
+                                                &effects,
+                                                &dictionary,
+                                                &scoring,
+                                                &awaiting
+                                            )),
+                                            SettingsText(action),
+                                        )],
+                                    ));
+                                }
+                            });
+                    }
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Node {
+                                    width: Val::Px(160.0),
+                                    ..default()
+                                },
+                                children![Text::new("Effects")],
+                            ));
+                            for effect in Effect::ALL {
+                                let action = SettingsButtonAction::Effect(effect);
+                                parent.spawn((
+                                    Button,
+                                    small_button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    action,
+                                    children![(
+                                        Text::new(settings_text(
+                                            action,
+                                            &bindings,
+                                            &effects,
+                                            &dictionary,
+                                            &scoring,
+                                            &awaiting
+                                        )),
+                                        SettingsText(action),
+                                    )],
+                                ));
+                            }
+                        });
+                    parent
+                        .spawn(Node {
+                            flex_direction: FlexDirection::Row,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        })
+                        .with_children(|parent| {
+                            parent.spawn((
+                                Node {
+                                    width: Val::Px(160.0),
+                                    ..default()
+                                },
+                                children![Text::new("Boogle")],
+                            ));
+                            for action in [
+                                SettingsButtonAction::Language,
+                                SettingsButtonAction::MinWordLength,
+                                SettingsButtonAction::Scoring,
+                            ] {
+                                parent.spawn((
+                                    Button,
+                                    small_button_node.clone(),
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    action,
+                                    children![(
+                                        Text::new(settings_text(
+                                            action,
+                                            &bindings,
+                                            &effects,
+                                            &dictionary,
+                                            &scoring,
+                                            &awaiting
+                                        )),
+                                        SettingsText(action),
+                                    )],
+                                ));
+                            }
+                        });
+                    for channel in VolumeChannel::ALL {
+                        let volume = audio.get(channel);
+                        parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::all(Val::Px(5.0)),
+                                ..default()
+                            })
+                            .with_children(|parent| {
+                                parent.spawn((
+                                    Node {
+                                        width: Val::Px(160.0),
+                                        ..default()
+                                    },
+                                    children![Text::new(channel.name())],
+                                ));
+                                parent.spawn((
+                                    Node {
+                                        width: Val::Px(SLIDER_WIDTH),
+                                        height: Val::Px(30.0),
+                                        ..default()
+                                    },
+                                    BackgroundColor(SLIDER_COLOR),
+                                    Interaction::default(),
+                                    RelativeCursorPosition::default(),
+                                    VolumeSlider(channel),
+                                    children![(
+                                        Node {
+                                            width: Val::Percent(volume * 100.0),
+                                            height: Val::Percent(100.0),
+                                            ..default()
+                                        },
+                                        BackgroundColor(NORMAL_BUTTON),
+                                        VolumeFill(channel),
+                                    )],
+                                ));
+                                parent.spawn((
+                                    Node {
+                                        width: Val::Px(80.0),
+                                        margin: UiRect::left(Val::Px(10.0)),
+                                        ..default()
+                                    },
+                                    children![(
+                                        Text::new(volume_text(volume)),
+                                        VolumeText(channel)
+                                    )],
+                                ));
+                            });
+                    }
+                    parent.spawn((
+                        Button,
+                        button_node.clone(),
+                        BackgroundColor(NORMAL_BUTTON),
+                        MenuButtonAction::Back,
+                        children![Text::new("Back")],
+                    ));
+                });
+        });
+}
+
+/// changes the device or the gamepad, key buttons wait for the next key
+/// effect buttons switch the effect on or off, the boogle buttons switch the words and the scoring
+#[allow(clippy::type_complexity)]
+fn settings_action(
+    interaction_query: Query<
+        (&Interaction, &SettingsButtonAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut bindings: ResMut<InputBindings>,
+    mut effects: ResMut<EffectSettings>,
+    mut dictionary: ResMut<DictionarySettings>,
+    mut scoring: ResMut<ScoringRules>,
+    mut awaiting: ResMut<AwaitingKey>,
+) {
+    for (interaction, action) in &interaction_query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        // every button saves the settings it changed
+        let saved = match *action {
+            SettingsButtonAction::Device(paddle) => {
+                let binding = bindings.get_mut(paddle);
+                binding.device = binding.device.next();
+                save_bindings(&bindings)
+            }
+            SettingsButtonAction::Gamepad(paddle) => {
+                let binding = bindings.get_mut(paddle);
+                binding.gamepad = (binding.gamepad + 1) % MAX_GAMEPADS;
+                save_bindings(&bindings)
+            }
+            // the key is saved by rebind_key when it was pressed
+            SettingsButtonAction::NegativeKey(_) | SettingsButtonAction::PositiveKey(_) => {
+                awaiting.0 = Some(*action);
+                Ok(())
+            }
+            SettingsButtonAction::Effect(effect) => {
+                effects.toggle(effect);
+                save_effect_settings(&effects)
+            }
+            SettingsButtonAction::Language => {
+                dictionary.language = dictionary.language.next();
+                save_dictionary_settings(&dictionary)
+            }
+            SettingsButtonAction::MinWordLength => {
+                scoring.next_min_word_length();
+                save_scoring_rules(&scoring)
+            }
+            SettingsButtonAction::Scoring => {
+                scoring.scheme = scoring.scheme.next();
+                save_scoring_rules(&scoring)
+            }
+        };
+        if let Err(e) = saved {
+            eprintln!("{}", e);
+        }
+    }
+}
+
+/// binds the next pressed key, escape keeps the old key
+fn rebind_key(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mut bindings: ResMut<InputBindings>,
+    mut awaiting: ResMut<AwaitingKey>,
+) {
+    let Some(action) = awaiting.0 else {
+        return;
+    };
+    let Some(key) = keyboard_input.get_just_pressed().next().copied() else {
+        return;
+    };
+
+    awaiting.0 = None;
+    if key == KeyCode::Escape {
+        return;
+    }
+    match action {
+        SettingsButtonAction::NegativeKey(paddle) => bindings.get_mut(paddle).negative = key,
+        SettingsButtonAction::PositiveKey(paddle) => bindings.get_mut(paddle).positive = key,
+        SettingsButtonAction::Device(_)
+        | SettingsButtonAction::Gamepad(_)
+        | SettingsButtonAction::Effect(_)
+        | SettingsButtonAction::Language
+        | SettingsButtonAction::MinWordLength
+        | SettingsButtonAction::Scoring => return,
+    }
+    if let Err(e) = save_bindings(&bindings) {
+        eprintln!("{}", e);
+    }
+}
+
+/// writes the current bindings, effects and boogle settings into the button texts
+fn update_settings_text(
+    bindings: Res<InputBindings>,
+    effects: Res<EffectSettings>,
+    dictionary: Res<DictionarySettings>,
+    scoring: Res<ScoringRules>,
+    awaiting: Res<AwaitingKey>,
+    mut texts: Query<(&mut Text, &SettingsText)>,
+) {
+    if !bindings.is_changed()
+        && !effects.is_changed()
+        && !dictionary.is_changed()
+        && !scoring.is_changed()
+        && !awaiting.is_changed()
+    {
+        return;
+    }
+    for (mut text, SettingsText(action)) in &mut texts {
+        text.0 = settings_text(
+            *action,
+            &bindings,
+            &effects,
+            &dictionary,
+            &scoring,
+            &awaiting,
+        );
+    }
+}
+
+/// a key binding which was not finished is dropped when the screen is left
+fn cancel_rebind(mut awaiting: ResMut<AwaitingKey>) {
+    awaiting.0 = None;
+}
+
+/// sets the volume to the position of the pointer while the slider is pressed
+fn volume_slider(
+    sliders: Query<(&Interaction, &RelativeCursorPosition, &VolumeSlider)>,
+    mut audio: ResMut<AudioSettings>,
+) {
+    for (interaction, cursor, VolumeSlider(channel)) in &sliders {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        if let Some(position) = cursor.normalized {
+            // steps of five percent
+            let volume = (position.x.clamp(0.0, 1.0) * 20.0).round() / 20.0;
+            if audio.get(*channel) != volume {
+                audio.set(*channel, volume);
+            }
+        }
+    }
+}
+
+/// writes the current volumes into the sliders
+fn update_volume_sliders(
+    audio: Res<AudioSettings>,
+    mut fills: Query<(&mut Node, &VolumeFill)>,
+    mut texts: Query<(&mut Text, &VolumeText)>,
+) {
+    if !audio.is_changed() {
+        return;
+    }
+    for (mut node, VolumeFill(channel)) in &mut fills {
+        node.width = Val::Percent(audio.get(*channel) * 100.0);
+    }
+    for (mut text, VolumeText(channel)) in &mut texts {
+        text.0 = volume_text(audio.get(*channel));
+    }
+}
+
+/// the volumes are saved when the screen is left, not on every step of a drag
+fn save_volume(audio: Res<AudioSettings>) {
+    if let Err(e) = save_audio_settings(&audio) {
+        eprintln!("{}", e);
+    }
+}